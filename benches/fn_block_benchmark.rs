@@ -2,20 +2,23 @@
 extern crate criterion;
 extern crate fn_block;
 
-#[macro_use]
 use fn_block::*;
-use criterion::{Criterion, Fun};
+use criterion::{black_box, Criterion, Fun};
+
+fn pure_option(o: Option<&str>) -> Option<String> {
+    o.and_then(|st| st.get(0..3)).map(|st| st.to_lowercase())
+}
 
-fn pure_option(o: Option<&str>) {
-    let s = o.and_then(|st| st.get(0..3)).map(|st| st.to_lowercase());
+fn closure(o: Option<&str>) -> Option<String> {
+    (|| Some(o?.get(0..3)?.to_lowercase()))()
 }
 
-fn closure(o: Option<&str>) {
-    let s = (|| Some(o?.get(0..3)?.to_lowercase()))();
+fn fn_expr_usage(o: Option<&str>) -> Option<String> {
+    fn_expr!{ o?.get(0..3)?.to_lowercase().into_some() }
 }
 
-fn fn_expr_usage(o: Option<&str>) {
-    let s = fn_expr!{ o?.get(0..3)?.to_lowercase().into_some() };
+fn fn_expr_annotated_usage(o: Option<&str>) -> Option<String> {
+    fn_expr!{ Option<String>: o?.get(0..3)?.to_lowercase().into_some() }
 }
 
 fn bench_option_combinators(c: &mut Criterion) {
@@ -29,10 +32,51 @@ fn bench_option_combinators(c: &mut Criterion) {
     c.bench_functions("Option combinations", functions, opt);
 }
 
+fn bench_fn_expr_annotation(c: &mut Criterion) {
+    let unannotated_fn = Fun::new("fn_expr Unannotated", |b, o| b.iter(|| fn_expr_usage(*o)));
+    let annotated_fn = Fun::new("fn_expr Annotated", |b, o| b.iter(|| fn_expr_annotated_usage(*o)));
+
+    let functions = vec![unannotated_fn, annotated_fn];
+    let opt = Some("Foobar");
+
+    c.bench_functions("fn_expr return type annotation", functions, opt);
+}
+
+fn bench_into_some_vs_some(c: &mut Criterion) {
+    c.bench_function("into_some u32", |b| b.iter(|| black_box(42u32).into_some()));
+    c.bench_function("Some u32", |b| b.iter(|| Some(black_box(42u32))));
+
+    c.bench_function("into_some String", |b| {
+        let s = "Foobar".to_string();
+        b.iter(|| black_box(s.clone()).into_some())
+    });
+    c.bench_function("Some String", |b| {
+        let s = "Foobar".to_string();
+        b.iter(|| Some(black_box(s.clone())))
+    });
+
+    c.bench_function("into_some [u64; 32]", |b| b.iter(|| black_box([7u64; 32]).into_some()));
+    c.bench_function("Some [u64; 32]", |b| b.iter(|| Some(black_box([7u64; 32]))));
+}
+
+fn bench_into_ok_vs_ok(c: &mut Criterion) {
+    c.bench_function("into_ok u32", |b| b.iter(|| IntoOk::<()>::into_ok(black_box(42u32))));
+    c.bench_function("Ok u32", |b| b.iter(|| Ok::<_, ()>(black_box(42u32))));
+
+    c.bench_function("into_ok String", |b| {
+        let s = "Foobar".to_string();
+        b.iter(|| IntoOk::<()>::into_ok(black_box(s.clone())))
+    });
+    c.bench_function("Ok String", |b| {
+        let s = "Foobar".to_string();
+        b.iter(|| Ok::<_, ()>(black_box(s.clone())))
+    });
+}
+
 criterion_group! {
     name = benches;
     config = Criterion::default().sample_size(500);
-    targets = bench_option_combinators
+    targets = bench_option_combinators, bench_fn_expr_annotation, bench_into_some_vs_some, bench_into_ok_vs_ok
 }
 
 criterion_main!(benches);
@@ -0,0 +1,62 @@
+#[macro_use]
+extern crate criterion;
+#[macro_use]
+extern crate fn_block;
+
+use criterion::{Criterion, Fun};
+use std::num::ParseIntError;
+
+enum ParseErr {
+    Int,
+}
+
+impl From<ParseIntError> for ParseErr {
+    fn from(_: ParseIntError) -> ParseErr {
+        ParseErr::Int
+    }
+}
+
+// Shape of the expansion of fn_try! up to version 0.2.1: the error is matched
+// a second time inside of the `Err` arm.
+fn nested_match(s: &str) -> u32 {
+    match (|| -> Result<u32, ParseErr> { Ok(s.parse::<u32>()? + 1) })() {
+        Ok(v) => v,
+        Err(e) => match e {
+            ParseErr::Int => 0,
+        },
+    }
+}
+
+fn fn_try_usage(s: &str) -> u32 {
+    fn_try! {
+        s.parse::<u32>()? + 1
+        => catch {
+            ParseErr::Int => 0
+        }
+    }
+}
+
+fn handwritten(s: &str) -> u32 {
+    match s.parse::<u32>() {
+        Ok(v) => v + 1,
+        Err(_) => 0,
+    }
+}
+
+fn bench_fn_try(c: &mut Criterion) {
+    let nested_fn = Fun::new("Nested Match", |b, s: &&str| b.iter(|| nested_match(s)));
+    let fn_try_fn = Fun::new("fn_try Single Match", |b, s: &&str| b.iter(|| fn_try_usage(s)));
+    let handwritten_fn = Fun::new("Handwritten Match", |b, s: &&str| b.iter(|| handwritten(s)));
+
+    let functions = vec![handwritten_fn, nested_fn, fn_try_fn];
+
+    c.bench_functions("fn_try error recovery", functions, "4x2");
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(500);
+    targets = bench_fn_try
+}
+
+criterion_main!(benches);
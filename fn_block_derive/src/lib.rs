@@ -0,0 +1,125 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Derive macros for the [`fn_block`](https://crates.io/crates/fn_block) crate.
+//!
+//! This crate is not meant to be used directly, the macros are re-exported by
+//! `fn_block` if its crate feature `derive` is enabled.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Expr, LitStr, Type};
+
+/// Derives an inherent method `into_some` for the annotated type, wrapping
+/// the value into a `Some`. The inherent method takes precedence over the
+/// method of the blanket implemented trait `fn_block::IntoSome` in method call
+/// syntax, so the behavior can be customized per type. With the attribute
+/// `#[into_some(none_if = "<expr>")]`, `None` is returned if the given
+/// expression, which can refer to `self`, evaluates to `true`.
+#[proc_macro_derive(IntoSome, attributes(into_some))]
+pub fn derive_into_some(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    into_some_impl(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn into_some_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let mut none_if: Option<Expr> = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("into_some")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("none_if") {
+                let cond: LitStr = meta.value()?.parse()?;
+                none_if = Some(cond.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported into_some attribute, expected `none_if`"))
+            }
+        })?;
+    }
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (doc, body) = match none_if {
+        Some(cond) => (
+            "This method moves `self` into an `Option::Some`, or returns `None` for a sentinel value.",
+            quote! {
+                if #cond {
+                    ::core::option::Option::None
+                } else {
+                    ::core::option::Option::Some(self)
+                }
+            },
+        ),
+        None => (
+            "This method moves `self` into an `Option::Some`.",
+            quote! { ::core::option::Option::Some(self) },
+        ),
+    };
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #doc]
+            #[inline(always)]
+            pub fn into_some(self) -> ::core::option::Option<Self> {
+                #body
+            }
+        }
+    })
+}
+
+/// Derives an inherent method `into_ok` for the annotated type, wrapping
+/// the value into an `Ok`. The inherent method takes precedence over the
+/// method of the blanket implemented trait `fn_block::IntoOk` in method call
+/// syntax. By default the error type is a type parameter of the method, so it
+/// can be given via turbofish. With the attribute `#[into_ok(error = "<type>")]`,
+/// the error type is fixed to the given type.
+#[proc_macro_derive(IntoOk, attributes(into_ok))]
+pub fn derive_into_ok(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    into_ok_impl(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn into_ok_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let mut error: Option<Type> = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("into_ok")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("error") {
+                let ty: LitStr = meta.value()?.parse()?;
+                error = Some(ty.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported into_ok attribute, expected `error`"))
+            }
+        })?;
+    }
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let method = match error {
+        Some(err) => quote! {
+            /// This method moves `self` into a `Result::Ok` and returns it.
+            #[inline(always)]
+            pub fn into_ok(self) -> ::core::result::Result<Self, #err> {
+                ::core::result::Result::Ok(self)
+            }
+        },
+        None => quote! {
+            /// This method moves `self` into a `Result::Ok` and returns it.
+            #[inline(always)]
+            pub fn into_ok<__E>(self) -> ::core::result::Result<Self, __E> {
+                ::core::result::Result::Ok(self)
+            }
+        },
+    };
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #method
+        }
+    })
+}
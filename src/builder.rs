@@ -0,0 +1,149 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Builder API as an alternative to the [`fn_expr!`] macro.
+//!
+//! The [`FnExprBuilder`] allows pinning the return type and the capture mode
+//! of a closure before calling it, without using a macro. This is e.g. useful
+//! inside of other macros, where nesting macro invocations is inconvenient.
+//!
+//! [`fn_expr!`]: ../macro.fn_expr.html
+//! [`FnExprBuilder`]: struct.FnExprBuilder.html
+
+use core::marker::PhantomData;
+
+/// Capture mode marker of a [`FnExprBuilder`], accepting closures
+/// which borrow from their environment. This is the initial capture mode.
+///
+/// [`FnExprBuilder`]: struct.FnExprBuilder.html
+pub struct Borrowing;
+
+/// Capture mode marker of a [`FnExprBuilder`], only accepting closures
+/// which do not borrow from their environment, i.e. `move` closures
+/// capturing owned values.
+///
+/// [`FnExprBuilder`]: struct.FnExprBuilder.html
+pub struct Moving;
+
+/// Return type marker of a [`FnExprBuilder`], leaving the return type
+/// to be inferred from the closure. This is the initial return type marker.
+///
+/// [`FnExprBuilder`]: struct.FnExprBuilder.html
+pub struct Inferred;
+
+/// Return type marker of a [`FnExprBuilder`], pinning the return type to `T`.
+///
+/// [`FnExprBuilder`]: struct.FnExprBuilder.html
+pub struct Returns<T>(PhantomData<fn() -> T>);
+
+/// This trait, which is implemented for the return type markers [`Inferred`]
+/// and [`Returns`], determines which return types the closure passed to
+/// the `run` method of a [`FnExprBuilder`] may have.
+///
+/// [`Inferred`]: struct.Inferred.html
+/// [`Returns`]: struct.Returns.html
+/// [`FnExprBuilder`]: struct.FnExprBuilder.html
+pub trait ReturnSpec<T> {}
+
+/// Implementation of trait `ReturnSpec` for
+/// the `Inferred` marker, allowing all return types.
+impl<T> ReturnSpec<T> for Inferred {}
+
+/// Implementation of trait `ReturnSpec` for
+/// the `Returns<T>` marker, only allowing the return type `T`.
+impl<T> ReturnSpec<T> for Returns<T> {}
+
+/// Builder for calling a closure with a pinned return type and capture mode.
+/// The builder itself holds no data, all settings are expressed in its type
+/// parameters: `R` is the return type marker ([`Inferred`] or [`Returns`]) and
+/// `C` the capture mode marker ([`Borrowing`] or [`Moving`]). The method `run`
+/// consumes the builder and calls the given closure directly, just like [`fn_expr!`] does.
+///
+/// Pinning the return type via `with_return` is useful when the closure uses the
+/// `?` operator, since the return type cannot be inferred from it. After calling
+/// `move_capture`, `run` only accepts closures not borrowing from their environment,
+/// so the compiler enforces that a `move` closure is used.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// let o = Some("Foobar".to_string());
+/// let s = FnExprBuilder::new()
+///     .move_capture()
+///     .with_return::<Option<String>>()
+///     .run(move || o?.get(0..3)?.to_lowercase().into_some());
+/// assert_eq!(Some("foo".to_string()), s);
+/// ```
+///
+/// A closure borrowing from its environment is rejected after `move_capture`:
+///
+/// ```compile_fail
+/// # use fn_block::*;
+/// let o = Some("Foobar".to_string());
+/// let len = FnExprBuilder::new().move_capture().run(|| o.as_ref().map(String::len));
+/// ```
+///
+/// [`Inferred`]: struct.Inferred.html
+/// [`Returns`]: struct.Returns.html
+/// [`Borrowing`]: struct.Borrowing.html
+/// [`Moving`]: struct.Moving.html
+/// [`fn_expr!`]: ../macro.fn_expr.html
+pub struct FnExprBuilder<R = Inferred, C = Borrowing> {
+    marker: PhantomData<fn() -> (R, C)>,
+}
+
+impl FnExprBuilder {
+    /// Creates a new builder with borrowing capture mode and a return type,
+    /// which is inferred from the closure passed to `run`.
+    pub fn new() -> FnExprBuilder {
+        FnExprBuilder { marker: PhantomData }
+    }
+}
+
+impl Default for FnExprBuilder {
+    fn default() -> FnExprBuilder {
+        FnExprBuilder::new()
+    }
+}
+
+impl<R, C> FnExprBuilder<R, C> {
+    /// Pins the return type of the closure passed to `run` to `T`.
+    pub fn with_return<T>(self) -> FnExprBuilder<Returns<T>, C> {
+        FnExprBuilder { marker: PhantomData }
+    }
+
+    /// Restricts the closure passed to `run` to closures not borrowing from
+    /// their environment.
+    pub fn move_capture(self) -> FnExprBuilder<R, Moving> {
+        FnExprBuilder { marker: PhantomData }
+    }
+}
+
+impl<R> FnExprBuilder<R, Borrowing> {
+    /// Consumes the builder and calls the given closure, returning its result.
+    #[inline]
+    pub fn run<T, F>(self, f: F) -> T
+    where
+        R: ReturnSpec<T>,
+        F: FnOnce() -> T,
+    {
+        f()
+    }
+}
+
+impl<R> FnExprBuilder<R, Moving> {
+    /// Consumes the builder and calls the given closure, returning its result.
+    /// The closure must not borrow from its environment.
+    #[inline]
+    pub fn run<T, F>(self, f: F) -> T
+    where
+        R: ReturnSpec<T>,
+        F: FnOnce() -> T + 'static,
+    {
+        f()
+    }
+}
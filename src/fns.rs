@@ -0,0 +1,283 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Free functions for constructing `Option` and `Result` values.
+//!
+//! These functions are useful when the value to be wrapped is not
+//! at the end of a call chain, so the extension traits of this crate
+//! cannot be used in method position.
+
+use core::mem::MaybeUninit;
+use core::num::NonZeroU32;
+
+/// Wraps the given value into a `Some`. This is the `const` counterpart of the
+/// trait method [`IntoSome::into_some`], which cannot be called in `const` contexts,
+/// since trait methods cannot be `const` on stable Rust.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::fns::into_some;
+/// const ANSWER: Option<u32> = into_some(42);
+/// assert_eq!(Some(42), ANSWER);
+/// ```
+///
+/// [`IntoSome::into_some`]: ../trait.IntoSome.html#tymethod.into_some
+#[inline(always)]
+pub const fn into_some<T>(value: T) -> Option<T> {
+    Some(value)
+}
+
+/// Wraps the given value into an `Ok`. This is the `const` counterpart of the
+/// trait method [`IntoOk::into_ok`], which cannot be called in `const` contexts,
+/// since trait methods cannot be `const` on stable Rust.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::fns::into_ok;
+/// const ANSWER: Result<u32, &str> = into_ok(42);
+/// assert_eq!(Ok(42), ANSWER);
+/// ```
+///
+/// [`IntoOk::into_ok`]: ../trait.IntoOk.html#tymethod.into_ok
+#[inline(always)]
+pub const fn into_ok<T, E>(value: T) -> Result<T, E> {
+    Ok(value)
+}
+
+/// Converts the given non-zero integer into a `NonZeroU32` and wraps it into a `Some`.
+/// This accepts all unsigned non-zero integer types losslessly convertible into a
+/// `NonZeroU32`, i.e. `NonZeroU8`, `NonZeroU16` and `NonZeroU32`.
+///
+/// Note that this is not a replacement for `NonZeroU32::new`: the value is already
+/// known to be non-zero, so the result is always a `Some`. `NonZeroU32::new(0)`,
+/// on the other hand, returns `None` for a zero value.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::fns::nonzero_some;
+/// use std::num::{NonZeroU8, NonZeroU32};
+/// let n = NonZeroU8::new(8).unwrap();
+/// assert_eq!(NonZeroU32::new(8), nonzero_some(n));
+/// ```
+#[inline]
+pub fn nonzero_some<T>(n: T) -> Option<NonZeroU32>
+where
+    T: Into<NonZeroU32>,
+{
+    Some(n.into())
+}
+
+/// Extracts the value of the given `MaybeUninit` and wraps it into a `Some`.
+/// Wrapping a `MaybeUninit` itself via [`IntoSome::into_some`] is always safe,
+/// but does not give access to the value; this function ends an initialization
+/// chain with the initialized value instead.
+///
+/// # Safety
+///
+/// The caller has to ensure that the `MaybeUninit` is fully initialized, as
+/// described for `MaybeUninit::assume_init`. Calling this function on
+/// uninitialized memory is undefined behavior.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::fns::into_some_assume_init;
+/// use std::mem::MaybeUninit;
+/// let mut value = MaybeUninit::<u32>::uninit();
+/// value.write(42);
+/// assert_eq!(Some(42), unsafe { into_some_assume_init(value) });
+/// ```
+///
+/// [`IntoSome::into_some`]: ../trait.IntoSome.html#tymethod.into_some
+#[inline(always)]
+pub unsafe fn into_some_assume_init<T>(mu: MaybeUninit<T>) -> Option<T> {
+    Some(mu.assume_init())
+}
+
+/// Converts the given `bool` into a `Result`, mapping `true` to `Ok(())` and
+/// `false` to `Err(())`. Note that [`IntoOk::into_ok`] called on a `bool` wraps
+/// the value itself into an `Ok`, which is rarely what is wanted for flags.
+/// The result can be combined with `Result::map_err` to attach a proper error.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::fns::bool_to_result;
+/// assert_eq!(Ok(()), bool_to_result(true));
+/// assert_eq!(Err("not ready"), bool_to_result(false).map_err(|_| "not ready"));
+/// ```
+///
+/// [`IntoOk::into_ok`]: ../trait.IntoOk.html#tymethod.into_ok
+#[inline(always)]
+#[allow(clippy::result_unit_err)]
+pub const fn bool_to_result(b: bool) -> Result<(), ()> {
+    if b {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+/// Returns `Some(value)` if the predicate `pred` holds true for the given value,
+/// and `None` otherwise. The predicate receives a reference to the value, so the
+/// value is not consumed before the wrapping decision.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::fns::some_if;
+/// assert_eq!(Some(42), some_if(42, |&i| i > 0));
+/// assert_eq!(None, some_if(-42, |&i| i > 0));
+/// ```
+pub fn some_if<T, F>(value: T, pred: F) -> Option<T>
+where
+    F: FnOnce(&T) -> bool,
+{
+    if pred(&value) {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Inverse of [`some_if`]: returns `None` if the predicate `pred` holds true for
+/// the given value, dropping the value, and `Some(value)` otherwise. This is useful
+/// for discarding values representing sentinel states.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::fns::none_if;
+/// assert_eq!(None, none_if(0, |&n| n == 0));
+/// assert_eq!(Some(3), none_if(3, |&n| n == 0));
+/// ```
+///
+/// [`some_if`]: fn.some_if.html
+pub fn none_if<T, F>(value: T, pred: F) -> Option<T>
+where
+    F: FnOnce(&T) -> bool,
+{
+    if pred(&value) {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Lazy variant of [`some_if`]: the value is created by calling `value_fn`,
+/// and then wrapped into a `Some` if the predicate `pred` holds true for it,
+/// or dropped (returning `None`) otherwise.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::fns::some_if_with;
+/// let s = some_if_with(|| "foo".to_uppercase(), |s| !s.is_empty());
+/// assert_eq!(Some("FOO".to_string()), s);
+/// ```
+///
+/// [`some_if`]: fn.some_if.html
+pub fn some_if_with<T, F, G>(value_fn: F, pred: G) -> Option<T>
+where
+    F: FnOnce() -> T,
+    G: FnOnce(&T) -> bool,
+{
+    some_if(value_fn(), pred)
+}
+
+/// Returns `Ok(value)` if the predicate `pred` holds true for the given value,
+/// and `Err(err)` otherwise. The predicate receives a reference to the value, so the
+/// value is not consumed before the wrapping decision.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::fns::ok_if;
+/// assert_eq!(Ok(42), ok_if(42, |&i| i > 0, "not positive"));
+/// assert_eq!(Err("not positive"), ok_if(-42, |&i| i > 0, "not positive"));
+/// ```
+pub fn ok_if<T, E, F>(value: T, pred: F, err: E) -> Result<T, E>
+where
+    F: FnOnce(&T) -> bool,
+{
+    if pred(&value) {
+        Ok(value)
+    } else {
+        Err(err)
+    }
+}
+
+/// Lazy variant of [`ok_if`]: the error is only created by calling `err_fn`,
+/// if the predicate `pred` does not hold true for the given value.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::fns::ok_if_else;
+/// let r = ok_if_else(-42, |&i| i > 0, || format!("{} is not positive", -42));
+/// assert_eq!(Err("-42 is not positive".to_string()), r);
+/// ```
+///
+/// [`ok_if`]: fn.ok_if.html
+pub fn ok_if_else<T, E, F, G>(value: T, pred: F, err_fn: G) -> Result<T, E>
+where
+    F: FnOnce(&T) -> bool,
+    G: FnOnce() -> E,
+{
+    if pred(&value) {
+        Ok(value)
+    } else {
+        Err(err_fn())
+    }
+}
+
+/// Inverse of [`ok_if`]: returns `Err(err)` if the predicate `pred` holds true for
+/// the given value, and `Ok(value)` otherwise. This matches validations where the
+/// predicate describes the error condition.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::fns::err_if;
+/// assert_eq!(Err("empty"), err_if("", |s| s.is_empty(), "empty"));
+/// assert_eq!(Ok("foo"), err_if("foo", |s| s.is_empty(), "empty"));
+/// ```
+///
+/// [`ok_if`]: fn.ok_if.html
+pub fn err_if<T, E, F>(value: T, pred: F, err: E) -> Result<T, E>
+where
+    F: FnOnce(&T) -> bool,
+{
+    if pred(&value) {
+        Err(err)
+    } else {
+        Ok(value)
+    }
+}
+
+/// Lazy variant of [`err_if`]: the value is created by calling `value_fn`,
+/// and then wrapped into an `Ok` if the predicate `pred` does not hold true for it,
+/// or dropped (returning `Err(err)`) otherwise.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::fns::err_if_else;
+/// let r = err_if_else(|| " foo ".trim().to_string(), |s| s.is_empty(), "empty");
+/// assert_eq!(Ok("foo".to_string()), r);
+/// ```
+///
+/// [`err_if`]: fn.err_if.html
+pub fn err_if_else<T, E, F, G>(value_fn: G, pred: F, err: E) -> Result<T, E>
+where
+    F: FnOnce(&T) -> bool,
+    G: FnOnce() -> T,
+{
+    err_if(value_fn(), pred, err)
+}
@@ -78,27 +78,183 @@
 //! use std::error::Error;
 //! struct ConvertErr();
 //! impl <T: Error> From<T> for ConvertErr {
-//! 	fn from(_: T) -> ConvertErr {ConvertErr()}
+//!     fn from(_: T) -> ConvertErr {ConvertErr()}
 //! }
 //! let bytes : &[u8] = &[0x0020,0x0034,0x0032];
+//! # #[cfg(feature = "unproven")] {
 //! let res_int = fn_try!{
-//! 	from_utf8(bytes)?.trim().parse::<u32>()?
-//! 	=> catch {
-//! 		ConvertErr() => 0u32
-//! 	}
+//!     from_utf8(bytes)?.trim().parse::<u32>()?
+//!     => catch {
+//!         ConvertErr() => 0u32
+//!     }
 //! };
 //! assert_eq!(res_int, 42);
+//! # }
 //! ```
 //! A more verbose and realistic version of the example above is available in
 //! the [`fn_try!`] documentation.
 //!
+//! ## `no_std` support
+//!
+//! The crate feature `std` is enabled by default. If it is disabled, the crate only
+//! depends on `core` and can be used in `no_std` environments, such as embedded or
+//! WebAssembly targets:
+//!
+//! ```toml
+//! [dependencies]
+//! fn_block = { version = "0.2.1", default-features = false }
+//! ```
+//!
+//! The macros and most traits do not need the standard library. Traits working with
+//! allocated types (e.g. [`IntoCowOwned`] or [`PartitionResults`]) and the parts of the
+//! unstable features depending on the standard library (e.g. [`fn_main_try!`] or the
+//! `timeout` form of [`fn_try!`]) are only available if the feature `std` is enabled.
+//! The unstable features formatting errors into messages (e.g. [`fn_try_expect!`] or the
+//! `log` modifier of [`fn_try_opt!`]) use the `alloc` crate without `std`, so they require
+//! a global allocator. The crate feature `backtrace` enables the feature `std`.
+//!
 //! [`fn_try!`]: macro.fn_try.html
+//! [`fn_main_try!`]: macro.fn_main_try.html
+//! [`fn_try_expect!`]: macro.fn_try_expect.html
+//! [`fn_try_opt!`]: macro.fn_try_opt.html
+//! [`IntoCowOwned`]: trait.IntoCowOwned.html
+//! [`PartitionResults`]: trait.PartitionResults.html
+
+#![cfg_attr(feature = "nightly-try-trait", feature(try_trait_v2))]
+#![cfg_attr(all(test, feature = "nightly-try-trait"), feature(try_trait_v2_residual))]
+#![cfg_attr(feature = "nightly-coroutines", feature(coroutine_trait, iter_from_coroutine))]
+#![cfg_attr(all(test, feature = "nightly-coroutines"), feature(coroutines))]
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+extern crate core;
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
+#[cfg(feature = "unproven")]
+extern crate alloc;
+
+#[cfg(feature = "log")]
+#[doc(hidden)]
+pub extern crate log;
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub extern crate tracing;
+#[cfg(feature = "either")]
+extern crate either;
+#[cfg(feature = "derive")]
+extern crate fn_block_derive;
+#[cfg(feature = "derive")]
+pub use fn_block_derive::{IntoOk, IntoSome};
+
+use core::cell::{Cell, RefCell};
+use core::ptr::NonNull;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::rc::Rc;
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex, RwLock};
+
+pub mod builder;
+pub mod fns;
+
+pub use builder::FnExprBuilder;
 
 ///////////////////////
 // Macro definitions //
 ///////////////////////
 
-/// *NOTE*: This macro has been deprecated! Use `fn_expr` instead
+/// This macro wraps a given rust block or expression into a closure and
+/// directly calls the closure. Optionally the return type of the
+/// closure can be specified first and separated with a colon from
+/// the body. This is the main entry point of this crate; the macros
+/// [`fn_expr!`] and [`fn_block!`] are aliases of this macro.
+///
+/// # Example with expression:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let o = Some("Foobar");
+/// let s = fn_scope!{ o?.get(0..3)?.to_lowercase().into_some() };
+/// assert_eq!("foo", s.unwrap());
+/// ```
+///
+/// # Example with block:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let o = Some("Foobar");
+/// let s = fn_scope!{{
+///   let foo = o?.get(0..3);
+///   Some(foo?.to_lowercase())
+/// }};
+/// assert_eq!("foo", s.unwrap());
+/// ```
+///
+/// # Example with return type:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::str::from_utf8;
+/// use std::error::Error;
+/// struct ConvertErr();
+/// impl <T: Error> From<T> for ConvertErr {
+///     fn from(_: T) -> ConvertErr {ConvertErr()}
+/// }
+/// let bytes : &[u8] = &[0x0020,0x0034,0x0032];
+/// let res_int = fn_scope!{Result<u32,ConvertErr>: {
+///     let str = from_utf8(bytes)?.trim();
+///     str.parse::<u32>()?.into_ok()
+/// }}.unwrap_or(0u32);
+/// assert_eq!(res_int, 42);
+/// ```
+///
+/// The return type may also be the never type `!`, if the wrapped block
+/// always diverges. The result can then be coerced to any type.
+///
+/// # Panic locations
+///
+/// The wrapped code keeps the source locations of the macro invocation, so a panic
+/// inside of the wrapped code (e.g. caused by `unwrap` or any other `#[track_caller]`
+/// function) reports the line of the invocation, not a location inside of this crate.
+/// The closure itself does show up as an additional frame in backtraces and when
+/// stepping through debug builds; attributes like `#[inline(always)]` or `#[track_caller]`
+/// cannot be put on closures on stable Rust.
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::panic::Location;
+///
+/// #[track_caller]
+/// fn location() -> &'static Location<'static> {
+///     Location::caller()
+/// }
+///
+/// let loc = fn_scope!{ location() }; let line = line!();
+/// assert_eq!(line, loc.line());
+/// ```
+///
+/// [`fn_expr!`]: macro.fn_expr.html
+/// [`fn_block!`]: macro.fn_block.html
+#[macro_export]
+macro_rules! fn_scope {
+    ($return_type:ty : { $($body:tt)* }) => {
+        (|| -> $return_type { $($body)* })()
+    };
+    ($return_type:ty : $body:expr) => {
+        (|| -> $return_type { $body })()
+    };
+    ($body:expr) => {
+        (|| $body)()
+    };
+}
+
+/// *NOTE*: This macro has been deprecated! Use [`fn_scope!`] instead
 ///
 /// # Intro
 ///
@@ -126,12 +282,12 @@
 /// use std::error::Error;
 /// struct ConvertErr();
 /// impl <T: Error> From<T> for ConvertErr {
-/// 	fn from(_: T) -> ConvertErr {ConvertErr()}
+///     fn from(_: T) -> ConvertErr {ConvertErr()}
 /// }
 /// let bytes : &[u8] = &[0x0020,0x0034,0x0032];
 /// let res_int = fn_block!{Result<u32,ConvertErr>: {
-/// 	let str = from_utf8(bytes)?.trim();
-/// 	str.parse::<u32>()?.into_ok()
+///     let str = from_utf8(bytes)?.trim();
+///     str.parse::<u32>()?.into_ok()
 /// }}.unwrap_or(0u32);
 /// assert_eq!(res_int, 42);
 /// ```
@@ -139,22 +295,25 @@
 /// Note that the examples use the traits [`IntoSome`] and [`IntoOk`],
 /// Defined in this crate.
 ///
+/// [`fn_scope!`]: macro.fn_scope.html
 /// [`IntoSome`]: trait.IntoSome.html
 /// [`IntoOk`]: trait.IntoOk.html
 #[macro_export]
 #[deprecated(
     since = "0.2.0",
-    note = "Please use `fn_expr` instead, since it can also be used to wrap blocks. Blocks are expressions as well."
+    note = "Please use `fn_scope` or `fn_expr` instead, since they can also be used to wrap blocks. Blocks are expressions as well."
 )]
 macro_rules! fn_block {
-    ($return_type:ty : $body:block) => {
-        (|| -> $return_type { $body })()
+    ($return_type:ty : { $($body:tt)* }) => {
+        $crate::fn_scope!($return_type: { $($body)* })
     };
-    ($body:block) => {
-        (|| $body)()
+    ({ $($body:tt)* }) => {
+        $crate::fn_scope!({ $($body)* })
     };
 }
 
+/// This macro is an alias of [`fn_scope!`].
+///
 /// This macro wraps a given rust code block into a closure and
 /// directly calls the closure. Optionally the return type of the
 /// closure can be specified first and separeted with a colon from
@@ -182,11 +341,11 @@ macro_rules! fn_block {
 /// use std::error::Error;
 /// struct ConvertErr();
 /// impl <T: Error> From<T> for ConvertErr {
-/// 	fn from(_: T) -> ConvertErr {ConvertErr()}
+///     fn from(_: T) -> ConvertErr {ConvertErr()}
 /// }
 /// let s : &[u8] = &[0x0020,0x0034,0x0032];
 /// let res_int = fn_expr!{ Result<u32,ConvertErr>:
-/// 	from_utf8(s)?.trim().parse::<u32>()?.into_ok()
+///     from_utf8(s)?.trim().parse::<u32>()?.into_ok()
 /// }.unwrap_or(0u32);
 /// assert_eq!(res_int, 42);
 /// ```
@@ -194,224 +353,4392 @@ macro_rules! fn_block {
 /// Note that the example use the trait [`IntoOk`],
 /// defined in this crate.
 ///
-/// [`IntoOk`]: trait.IntoOk.html
-#[macro_export]
-macro_rules! fn_expr {
-    ($return_type:ty : $body:expr) => {
-        (|| -> $return_type { $body })()
-    };
-    ($body:expr) => {
-        (|| $body)()
-    };
-}
-
-/// This macro wraps a given rust code expression into a closure and
-/// directly calls the closure. The result type of the expression is expected
-/// to be an "unwrapped" sucess value (not a `Result` type).
-/// The error case (a failing case of a `?` operator) *must* be handled
-/// (and recovered to a success type value) by a following `=> catch` block.
-/// - *Note 1*: Under the hood the result value of the expression will automatically wrapped
-///   into a `Result::Ok`, which is different from how the `fn_expr` and `fn_block` macros work!
-/// - *Note 2*: This macro is an unstable API to make use of it, enable the crate feature "unproven".
+/// # Example with diverging block:
 ///
-/// # Example:
-/// ```
+/// The return type may also be the never type `!`, if the wrapped block
+/// always diverges. The result can then be coerced to any type.
+///
+/// ```rust
 /// # #[macro_use]
 /// # use fn_block::*;
-/// use std::num::ParseIntError;
-/// use std::str::Utf8Error;
-/// use std::str::from_utf8;
-///
-/// enum ConvertErr {
-/// 	StrParseErr,
-/// 	IntParseErr
-/// }
-///
-/// impl From<Utf8Error> for ConvertErr {
-/// 	fn from(_: Utf8Error) -> ConvertErr {
-/// 		ConvertErr::StrParseErr
-/// 	}
-/// }
-/// impl From<ParseIntError> for ConvertErr {
-/// 	fn from(_: ParseIntError) -> ConvertErr {
-/// 		ConvertErr::IntParseErr
-/// 	}
-/// }
-///
-/// let s: &[u8] = &[0x0020, 0x0034, 0x0032];
-/// let i = fn_try! {
-///     from_utf8(s)?.trim().parse::<u32>()?
-/// 	=> catch {
-/// 		ConvertErr::StrParseErr => 0u32,
-/// 		ConvertErr::IntParseErr => u32::max_value()
-/// 	}
+/// let o = Some(42u32);
+/// let i: u32 = match o {
+///     Some(i) => i,
+///     None => fn_expr!{ !: { panic!("no value") } },
 /// };
 /// assert_eq!(42, i);
 /// ```
-/// Depending on the error type used in the catch block the type is inferred
-/// which error type the errors raised in the closure are converted into. This
-/// is part of the `?` operator semantics. Note that this also implies, that
-/// the `_` pattern cannot be used as the only catch pattern for the error,
-/// since in this case the error type cannot be inferred.
-///
-/// It is advised to use a crate like [`failure`] for error management/conversion.
-///
-/// # Note of Caution
-///
-/// Note that this API may be subject of change! The names may change, and the
-/// automatic wrapping of result value may disappear. This functionality may be
-/// controversial and feedback is welcome if this functionality should stay.
 ///
-/// # Internal workings
-///
-/// The returned `Result` from the closure will be matched. If an `Ok` is
-/// wrapped return value will be returned from the `fn_try`. If the returned
-/// result wrapps an error, the error type must be handled by the `=> catch` block
-/// Following the expression given by the user. This is basically a match block where
-/// the user has to define recovery cases matching error types to the success return type.
-///
-/// # Design descisions
-///
-/// The name of the macro and the `=> catch` block are chosen to be similar to the ones
-/// chosen for [RFC 2388] and should still work with the "Rust 2018 Edition".
-/// Unfortunately the macro name may confuse users of the deprecated `try!`
-/// macro, but it looks similar to `try`/`catch` blocks in other languages. The automatic
-/// wrapping of the sucessful result value into a `Result::Ok` may also be controversial
-/// and even the author is not entirely sure if this is the best way to model the API.
-/// However, [RFC 2388] already seems to settle on the automatic wrapping and the resulting
-/// code may look more familiar to people comming from other languages.
-///
-/// [RFC 2388]: https://rust-lang.github.io/rfcs/2388-try-expr.html
-/// [`failure`]: https://crates.io/crates/failure
+/// [`fn_scope!`]: macro.fn_scope.html
+/// [`IntoOk`]: trait.IntoOk.html
 #[macro_export]
-#[cfg(feature = "unproven")]
-macro_rules! fn_try {
-	($body:expr => catch {
-		$($err_pat:pat => $pat_bod:expr),+
-	}) => {
-		match (|| { Ok($body) })() {
-			Ok(v) => v,
-			Err(e) => match e {
-				$($err_pat => $pat_bod),+
-			}
-		}
-	};
+macro_rules! fn_expr {
+    ($($input:tt)+) => {
+        $crate::fn_scope!($($input)+)
+    };
 }
 
-///////////////////////
-// Trait definitions //
-///////////////////////
-
-/// This trait, which is implemented for all sized types,
-/// provides the method `into_some`, which moves the
-/// value on which it is called into an `Optional::Some`.
-/// This is particularly useful when having to wrap a value into
-/// a `Some` at the end of a call chain.
+/// This macro works like [`fn_expr!`], but enforces that the wrapped block or
+/// expression only borrows the data it captures from its environment. The generated
+/// closure is required to implement `Fn`, so moving a captured value (e.g. applying
+/// `?` to an owned `Option` from the environment) or mutating captured data is rejected
+/// by the compiler. This documents and enforces the intent that all captured values are
+/// still usable after the macro. Like for [`fn_expr!`], the result type can optionally
+/// be given first, separated with a colon from the body.
 ///
 /// # Example:
 ///
 /// ```rust
 /// # #[macro_use]
 /// # use fn_block::*;
-/// let o : Option<String> = "foo bar ".trim().to_uppercase().into_some();
-/// assert_eq!("FOO BAR", o.unwrap());
+/// let name = Some("Foobar".to_string());
+/// let prefix = fn_expr_ref!{ name.as_ref()?.get(0..3)?.to_lowercase().into_some() };
+/// assert_eq!("foo", prefix.unwrap());
+/// assert_eq!("Foobar", name.unwrap());
 /// ```
 ///
-/// This can e.g. be used inside of
-/// an expression wrapped in a [`fn_expr!`] or [`fn_block!`] macro.
-///
-/// # Example using `fn_expr!`:
+/// Moving a captured value out of the environment does not compile:
 ///
-/// ```rust
+/// ```compile_fail
 /// # #[macro_use]
 /// # use fn_block::*;
-/// let o = Some("Foobar");
-/// let s = fn_expr!{ o?.get(0..3)?.to_lowercase().into_some() };
-/// assert_eq!("foo", s.unwrap());
+/// let name = Some("Foobar".to_string());
+/// let prefix = fn_expr_ref!{ name?.get(0..3)?.to_lowercase().into_some() };
 /// ```
 ///
 /// [`fn_expr!`]: macro.fn_expr.html
-/// [`fn_block!`]: macro.fn_block.html
-pub trait IntoSome: Sized {
-    fn into_some(self) -> Option<Self>;
+#[macro_export]
+macro_rules! fn_expr_ref {
+    ($return_type:ty : { $($body:tt)* }) => {
+        $crate::__private::call_ref(|| -> $return_type { $($body)* })
+    };
+    ($return_type:ty : $body:expr) => {
+        $crate::__private::call_ref(|| -> $return_type { $body })
+    };
+    ($body:expr) => {
+        $crate::__private::call_ref(|| $body)
+    };
 }
 
-/// Implementration of trait `IntoSome` for
-/// all sized types.
+/// This macro works like [`fn_expr!`], but automatically wraps the value of the
+/// given expression (or the final expression of the given statements) into a `Some`.
+/// So a call chain using the `?` operator does not have to be terminated with
+/// a call to `into_some()`, which is easy to forget.
 ///
-/// # Example
+/// # Example:
 ///
 /// ```rust
-/// # use fn_block::IntoSome;
-/// let five = 5.into_some();
-/// assert_eq!(Some(5), five);
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let o = Some("Foobar");
+/// let s = fn_expr_some!{ o?.get(0..3)?.to_lowercase() };
+/// assert_eq!(Some("foo".to_string()), s);
 /// ```
 ///
-impl<T> IntoSome for T {
-    /// This method moves `self` into a `Some` and returns it.
-    fn into_some(self) -> Option<Self> {
-        Some(self)
-    }
+/// [`fn_expr!`]: macro.fn_expr.html
+#[macro_export]
+macro_rules! fn_expr_some {
+    ($($body:tt)+) => {
+        (|| { let v = { $($body)+ }; Some(v) })()
+    };
 }
 
-/// This trait, which is implemented for all sized types,
-/// provides the method `into_ok`, which moves the
-/// value on which it is called into an `Result::Ok`.
-/// This is particularly useful when having to wrap a value into
-/// an `Ok` at the end of a call chain.
+/// This macro works like [`fn_expr!`], but automatically wraps the value of the
+/// given expression (or the final expression of the given statements) into an `Ok`.
+/// The error type has to be inferable from the context, e.g. from the type of the
+/// variable the result is assigned to.
 ///
 /// # Example:
 ///
 /// ```rust
 /// # #[macro_use]
 /// # use fn_block::*;
-/// let res : Result<String,()> = "foo bar ".trim().to_uppercase().into_ok();
-/// assert_eq!("FOO BAR", res.unwrap());
+/// use std::num::ParseIntError;
+/// let s = "21";
+/// let i: Result<u32, ParseIntError> = fn_expr_ok!{ s.parse::<u32>()? * 2 };
+/// assert_eq!(Ok(42), i);
 /// ```
 ///
-/// This can e.g. be used inside of
-/// an expression wrapped in a [`fn_expr!`] or [`fn_block!`] macro.
+/// [`fn_expr!`]: macro.fn_expr.html
+#[macro_export]
+macro_rules! fn_expr_ok {
+    ($($body:tt)+) => {
+        (|| { let v = { $($body)+ }; Ok(v) })()
+    };
+}
+
+/// This macro works like [`fn_expr!`] for expressions evaluating to a `Result`,
+/// but attaches the given context string to the error, if the expression fails.
+/// The error type has to implement the [`AddContext`] trait, which defines how the
+/// context is attached. This aids diagnostics, similar to the `Context` trait of
+/// the `anyhow` crate. Like for [`fn_expr!`], the result type can optionally be
+/// given after the context.
 ///
-/// # Example using `fn_expr!`:
+/// # Example:
 ///
 /// ```rust
 /// # #[macro_use]
 /// # use fn_block::*;
-/// use std::str::from_utf8;
-/// use std::error::Error;
-/// struct ConvertErr();
-/// impl <T: Error> From<T> for ConvertErr {
-/// 	fn from(_: T) -> ConvertErr {ConvertErr()}
+/// use std::num::ParseIntError;
+///
+/// #[derive(Debug)]
+/// struct ConfigErr {
+///     context: Vec<String>,
 /// }
-/// let s : &[u8] = &[0x0020,0x0034,0x0032];
-/// let res_int = fn_expr!{ Result<u32,ConvertErr>:
-/// 	from_utf8(s)?.trim().parse::<u32>()?.into_ok()
-/// }.unwrap_or(0u32);
-/// assert_eq!(res_int, 42);
+///
+/// impl From<ParseIntError> for ConfigErr {
+///     fn from(_: ParseIntError) -> ConfigErr {
+///         ConfigErr { context: Vec::new() }
+///     }
+/// }
+///
+/// impl AddContext for ConfigErr {
+///     fn add_context(mut self, context: &str) -> ConfigErr {
+///         self.context.push(context.to_string());
+///         self
+///     }
+/// }
+///
+/// let res = fn_expr_ctx!{ "loading config": Result<u32, ConfigErr>:
+///     "x".parse::<u32>()?.into_ok()
+/// };
+/// assert_eq!(vec!["loading config".to_string()], res.unwrap_err().context);
 /// ```
 ///
 /// [`fn_expr!`]: macro.fn_expr.html
-/// [`fn_block!`]: macro.fn_block.html
-pub trait IntoOk<E>: Sized {
-    /// This method moves `self` into an `Ok` and returns it.
-    fn into_ok(self) -> Result<Self, E>;
+/// [`AddContext`]: trait.AddContext.html
+#[macro_export]
+macro_rules! fn_expr_ctx {
+    ($context:literal : $($input:tt)+) => {
+        $crate::__private::with_context($crate::fn_scope!($($input)+), $context)
+    };
 }
 
-/// Implementration of trait `IntoOk` for
-/// all sized types.
+/// This macro works like [`fn_expr_ok!`] with a declared error type, but allows
+/// applying the `?` operator to `Option` values as well. The macro is given the
+/// error type of the resulting `Result`, which has to implement `Default`,
+/// followed by a colon and the expression or block to evaluate. The value of the
+/// expression is wrapped into an `Ok`. Every `?` in the expression is rewritten,
+/// so that a `None` is converted into the default value of the error type via the
+/// [`OptionToResult`] trait before being propagated. Errors of `Result` values are
+/// converted into the error type via `From`, just like the `?` operator always does.
 ///
-/// # Example
+/// # Example:
 ///
 /// ```rust
-/// # use fn_block::IntoOk;
-/// let five : Result<u32,()> = 5.into_ok();
-/// assert_eq!(Ok(5), five);
-/// ```
-///
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+///
+/// #[derive(Debug, Default, PartialEq)]
+/// struct MissingErr;
+///
+/// impl From<ParseIntError> for MissingErr {
+///     fn from(_: ParseIntError) -> MissingErr {
+///         MissingErr
+///     }
+/// }
+///
+/// let o = Some("42 is the answer");
+/// let res = fn_expr_res!{ MissingErr: o?.get(0..2)?.parse::<u32>()? };
+/// assert_eq!(Ok(42), res);
+///
+/// let o = Some("4");
+/// let res = fn_expr_res!{ MissingErr: o?.get(0..2)?.parse::<u32>()? };
+/// assert_eq!(Err(MissingErr), res);
+/// ```
+///
+/// The rewriting is done token by token, so very long expressions may hit the
+/// macro recursion limit. In this case the limit can be raised via the
+/// `#![recursion_limit]` crate attribute.
+///
+/// [`fn_expr_ok!`]: macro.fn_expr_ok.html
+/// [`OptionToResult`]: trait.OptionToResult.html
+#[macro_export]
+macro_rules! fn_expr_res {
+    ($err:ty : $($body:tt)+) => {
+        $crate::__rewrite_missing!(@rewrite
+            (<$err as $crate::__private::Default>::default()) (result $err) [] [] $($body)+)
+    };
+}
+
+/// This macro is a `const` counterpart of [`fn_expr!`]: instead of wrapping the given
+/// block or expression into a closure, it is evaluated in an inline `const` block.
+/// It can therefore be used in `const` and `static` initializers, as long as the
+/// expression only consists of `const fn` calls and `const` operations. Like for
+/// [`fn_expr!`], the result type can optionally be given.
+///
+/// Note that the `?` operator is not usable in `const` contexts, so early returns
+/// have to be written with `match` or `if let` and `return` is not supported either.
+/// The macro requires Rust 1.79 or newer.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// const LEN: usize = const_fn_expr!{ usize: {
+///     let name = "fn_block";
+///     match name.len().checked_mul(2) {
+///         Some(len) => len,
+///         None => 0,
+///     }
+/// }};
+/// assert_eq!(16, LEN);
+/// ```
+///
+/// [`fn_expr!`]: macro.fn_expr.html
+#[macro_export]
+macro_rules! const_fn_expr {
+    ($return_type:ty : { $($body:tt)* }) => {
+        const { let v: $return_type = { $($body)* }; v }
+    };
+    ($return_type:ty : $body:expr) => {
+        const { let v: $return_type = $body; v }
+    };
+    ($body:expr) => {
+        const { $body }
+    };
+}
+
+/// This macro combines the values of all given `Option` expressions into
+/// a `Some` holding a tuple of all values, if all expressions evaluate to a `Some`.
+/// The expressions are evaluated from left to right and the evaluation stops at
+/// the first expression evaluating to `None`, in which case the macro evaluates to `None`.
+/// For combining `Option` values that are already evaluated, see the methods
+/// [`zip3`] and [`zip4`] of the [`OptionExt`] trait.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let host = Some("localhost");
+/// let port = "8080".parse::<u16>().ok();
+/// let all = zip_all!(host, port, Some(true));
+/// assert_eq!(Some(("localhost", 8080, true)), all);
+///
+/// let none = zip_all!(host, None::<u16>, Some(true));
+/// assert_eq!(None, none);
+/// ```
+///
+/// [`OptionExt`]: trait.OptionExt.html
+/// [`zip3`]: trait.OptionExt.html#tymethod.zip3
+/// [`zip4`]: trait.OptionExt.html#tymethod.zip4
+#[macro_export]
+macro_rules! zip_all {
+    ($($opt:expr),+ $(,)*) => {
+        (|| Some(($($opt?,)+)))()
+    };
+}
+
+/// This macro searches for the first successful candidate among multiple expressions.
+/// Each candidate is given as `try <expression>;` and is evaluated in its own closure,
+/// so the `?` operator only short-circuits the evaluation of that candidate. If a candidate
+/// evaluates to a `Some` (or an `Ok`), the macro returns it immediately and the following
+/// candidates are not evaluated. If no candidate succeeds, the macro evaluates to the
+/// last expression, which is not prefixed with `try`, e.g. a `None` or a default `Err`.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::collections::HashMap;
+/// let mut env = HashMap::new();
+/// env.insert("PORT", "80x");
+/// env.insert("FALLBACK_PORT", "8080");
+/// let port = fn_search!{
+///     try env.get("PORT")?.parse::<u16>().ok();
+///     try env.get("FALLBACK_PORT")?.parse::<u16>().ok();
+///     None
+/// };
+/// assert_eq!(Some(8080), port);
+/// ```
+#[macro_export]
+macro_rules! fn_search {
+    (@search [$($candidate:expr;)*] try $next:expr; $($rest:tt)+) => {
+        $crate::fn_search!(@search [$($candidate;)* $next;] $($rest)+)
+    };
+    (@search [$($candidate:expr;)+] $fallback:expr) => {
+        (|| {
+            $(
+                let found = (|| $candidate)();
+                if $crate::__private::SearchHit::is_hit(&found) {
+                    return found;
+                }
+            )+
+            $fallback
+        })()
+    };
+    (try $($body:tt)+) => {
+        $crate::fn_search!(@search [] try $($body)+)
+    };
+}
+
+/// This macro wraps a given rust code expression into a closure and
+/// directly calls the closure. The result type of the expression is expected
+/// to be an "unwrapped" sucess value (not a `Result` type).
+/// The error case (a failing case of a `?` operator) *must* be handled
+/// (and recovered to a success type value) by a following `=> catch` block.
+/// - *Note 1*: Under the hood the result value of the expression will automatically wrapped
+///   into a `Result::Ok`, which is different from how the `fn_expr` and `fn_block` macros work!
+/// - *Note 2*: This macro is an unstable API to make use of it, enable the crate feature "unproven".
+///
+/// # Example:
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+/// use std::str::Utf8Error;
+/// use std::str::from_utf8;
+///
+/// enum ConvertErr {
+///     StrParseErr,
+///     IntParseErr
+/// }
+///
+/// impl From<Utf8Error> for ConvertErr {
+///     fn from(_: Utf8Error) -> ConvertErr {
+///         ConvertErr::StrParseErr
+///     }
+/// }
+/// impl From<ParseIntError> for ConvertErr {
+///     fn from(_: ParseIntError) -> ConvertErr {
+///         ConvertErr::IntParseErr
+///     }
+/// }
+///
+/// let s: &[u8] = &[0x0020, 0x0034, 0x0032];
+/// let i = fn_try! {
+///     from_utf8(s)?.trim().parse::<u32>()?
+///     => catch {
+///         ConvertErr::StrParseErr => 0u32,
+///         ConvertErr::IntParseErr => u32::MAX
+///     }
+/// };
+/// assert_eq!(42, i);
+/// ```
+/// The patterns of the catch block can be any patterns allowed in a `match` arm without
+/// guard, including struct variant patterns with `..`, tuple variant patterns with `_`,
+/// `@` bindings, `ref` bindings and reference patterns, so payloads of the error can be
+/// used for the recovery.
+///
+/// Depending on the error type used in the catch block the type is inferred
+/// which error type the errors raised in the closure are converted into. This
+/// is part of the `?` operator semantics. Note that this also implies, that
+/// the `_` pattern cannot be used as the only catch pattern for the error,
+/// since in this case the error type cannot be inferred.
+///
+/// It is advised to use a crate like [`failure`] for error management/conversion.
+///
+/// # Block body
+///
+/// Instead of a single expression, a block of statements can be given. The value of the
+/// block's tail expression is wrapped into an `Ok`, so `let` bindings (including shadowing)
+/// and early returns via `?` can be used in the statements before.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+/// use std::str::{from_utf8, Utf8Error};
+///
+/// enum ConvertErr {
+///     StrParseErr,
+///     IntParseErr
+/// }
+/// # impl From<Utf8Error> for ConvertErr {
+/// #     fn from(_: Utf8Error) -> ConvertErr { ConvertErr::StrParseErr }
+/// # }
+/// # impl From<ParseIntError> for ConvertErr {
+/// #     fn from(_: ParseIntError) -> ConvertErr { ConvertErr::IntParseErr }
+/// # }
+///
+/// let bytes: &[u8] = &[0x0020, 0x0034, 0x0032];
+/// let i = fn_try! {{
+///     let s = from_utf8(bytes)?;
+///     let s = s.trim();
+///     s.parse::<u32>()?
+/// } => catch {
+///     ConvertErr::StrParseErr => 0u32,
+///     ConvertErr::IntParseErr => u32::MAX
+/// }};
+/// assert_eq!(42, i);
+/// ```
+///
+/// # Layered recovery
+///
+/// A catch arm can itself be a `fn_try!`, e.g. to try a secondary fallible path if the
+/// primary one failed. The inner macro has its own closure and catch block, so errors
+/// raised in the inner expression are handled by the inner catch block, and bindings of
+/// the outer pattern can be used in the inner expression. Since the bindings introduced
+/// by the expansion are hygienic, they never collide with bindings of the user's code.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+///
+/// let primary = "x";
+/// let secondary = "0x2A";
+/// let i = fn_try! {
+///     primary.parse::<u32>()?
+///     => catch {
+///         ParseIntError { .. } => fn_try! {
+///             u32::from_str_radix(secondary.trim_start_matches("0x"), 16)?
+///             => catch {
+///                 ParseIntError { .. } => 0u32
+///             }
+///         }
+///     }
+/// };
+/// assert_eq!(42, i);
+/// ```
+///
+/// # Error hook
+///
+/// The `=> catch` block can be followed by an `on_error` clause holding a closure.
+/// The closure receives a reference to every error raised in the expression, before
+/// the error is matched against the catch block, regardless of which pattern handles
+/// the error. This is e.g. useful for counting recoveries. The result of the closure
+/// is ignored, the closure cannot influence which value is returned from the macro.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+///
+/// let mut recoveries = 0;
+/// for input in &["1", "x", "3"] {
+///     let _i: u32 = fn_try! {
+///         input.parse::<u32>()?
+///         => catch {
+///             ParseIntError { .. } => 0u32
+///         } on_error |_e| recoveries += 1
+///     };
+/// }
+/// assert_eq!(1, recoveries);
+/// ```
+///
+/// # Typed catch sections
+///
+/// Instead of a single `=> catch` block matching on a user defined error type, a sequence
+/// of `catch <ErrorType> as <binding> { ... }` sections can be given. The macro then
+/// generates a hidden error enum with one variant per listed error type and implements
+/// `From` for each of them, so no error type has to be defined by the user. Each section
+/// is executed when the expression failed with the respective error type.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+/// use std::str::{from_utf8, Utf8Error};
+///
+/// let s: &[u8] = &[0x0020, 0x0034, 0x0032];
+/// let i = fn_try! {
+///     from_utf8(s)?.trim().parse::<u32>()?
+///     => catch Utf8Error as _e { 0u32 }
+///        catch ParseIntError as _e { u32::MAX }
+/// };
+/// assert_eq!(42, i);
+/// ```
+/// Since the generated enum and its `From` implementations are items, the listed error
+/// types must be concrete types (they cannot refer to generic parameters of a surrounding
+/// function) and must be distinct from each other. Up to eight catch sections are supported.
+///
+/// # Catch handler
+///
+/// Instead of a block of patterns, `=> catch` can be followed by any expression implementing
+/// `FnOnce(E) -> T`, e.g. the name of a shared recovery function or a closure. The handler
+/// is called with every error raised in the expression and its result becomes the result
+/// of the macro. The argument type of the handler determines the error type the errors are
+/// converted into, so the argument of a closure has to be annotated with the error type.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+///
+/// fn recover(_: ParseIntError) -> u32 {
+///     0
+/// }
+///
+/// let i = fn_try! { "x".parse::<u32>()? + 1 => catch recover };
+/// assert_eq!(0, i);
+/// let i = fn_try! { "x".parse::<u32>()? + 1 => catch |_: ParseIntError| 42 };
+/// assert_eq!(42, i);
+/// ```
+///
+/// # Backtraces
+///
+/// Instead of a block of patterns, `=> catch (<error>, <trace>)` can be followed by a block
+/// in which the error is bound to the first pattern and a [`CaughtBacktrace`] to the second.
+/// If the crate feature `backtrace` is enabled, the backtrace is captured via
+/// `std::backtrace::Backtrace::capture()` directly after the expression failed, so it is
+/// only actually captured if backtraces are enabled via the `RUST_BACKTRACE` or
+/// `RUST_LIB_BACKTRACE` environment variables. Without the feature, the placeholder type
+/// [`CaughtBacktrace`] is a zero sized type, so the same code compiles regardless of the
+/// feature. Since the block does not consist of patterns, the error type has to be inferable
+/// from the block, e.g. by matching on the error.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+///
+/// let i = fn_try! {
+///     "x".parse::<u32>()?
+///     => catch (err, trace) {
+///         let _report = format!("{}\n{}", err, trace);
+///         match err {
+///             ParseIntError { .. } => 0u32
+///         }
+///     }
+/// };
+/// assert_eq!(0, i);
+/// ```
+///
+/// # Collecting errors
+///
+/// For validation style code, errors can be collected instead of being recovered from one
+/// at a time: `=> collect_errors into <sink>; else <default>` pushes every error raised in
+/// the expression into the given sink, which is a `Vec` (or a mutable reference to a `Vec`)
+/// in scope, and evaluates to the `else` expression. The element type of the sink determines
+/// the error type the errors are converted into. This way, several `fn_try!` invocations can
+/// build up a report of everything that went wrong.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+///
+/// let mut errs: Vec<ParseIntError> = Vec::new();
+/// let width = fn_try! { "x".parse::<u32>()? => collect_errors into errs; else 80 };
+/// let height = fn_try! { "24".parse::<u32>()? => collect_errors into errs; else 25 };
+/// assert_eq!((80, 24), (width, height));
+/// assert_eq!(1, errs.len());
+/// ```
+///
+/// # Catching the error message
+///
+/// If the recovery only needs a textual description of the error, the catch block can
+/// be replaced by `=> catch display <closure>`. Every error raised in the expression
+/// is formatted via its `Display` implementation and the resulting `String` is passed
+/// to the given closure, whose result becomes the result of the macro. Since the
+/// errors are formatted right away, the error type does not need to be inferred and
+/// the errors only have to implement `Display`.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::str::from_utf8;
+///
+/// let s: &[u8] = &[0x0020, 0x005A, 0x0032];
+/// let msg = fn_try! {
+///     from_utf8(s)?.trim().parse::<u32>()?.to_string()
+///     => catch display |msg| format!("failed: {}", msg)
+/// };
+/// assert_eq!("failed: invalid digit found in string", msg);
+/// ```
+///
+/// # Tracing
+///
+/// If the crate feature `tracing` is enabled, the expression can be prefixed with
+/// `span "<name>":`. The closure is then called inside of a [`tracing`] span with
+/// the given name (on level `INFO`) and, if the expression fails, an `ERROR` event
+/// holding the `Debug` representation of the error is recorded in the span before the
+/// catch block is executed. The error type must therefore implement `Debug`.
+/// Without the `tracing` feature, the `span` form is rejected with a compile error.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// #[derive(Debug)]
+/// enum ConfigErr {
+///     NotANumber
+/// }
+///
+/// impl From<std::num::ParseIntError> for ConfigErr {
+///     fn from(_: std::num::ParseIntError) -> ConfigErr {
+///         ConfigErr::NotANumber
+///     }
+/// }
+///
+/// # #[cfg(feature = "tracing")] {
+/// let timeout = fn_try! {
+///     span "load_config": "30".parse::<u32>()?
+///     => catch {
+///         ConfigErr::NotANumber => 60u32
+///     }
+/// };
+/// assert_eq!(30, timeout);
+/// # }
+/// ```
+///
+/// # Custom `Try` types
+///
+/// If the crate feature `nightly-try-trait` is enabled (which requires a nightly
+/// compiler), the expression can be prefixed with `as <Type>:`, where the type implements
+/// the unstable `std::ops::Try` trait. The result value of the expression is then wrapped
+/// via `Try::from_output` instead of `Result::Ok`, and the catch block matches on the
+/// *residual* of the type (e.g. `Result<Infallible, E>` for `Result<T, E>`), as produced
+/// by `Try::branch`. Without the `nightly-try-trait` feature, this form is rejected with a
+/// compile error.
+///
+/// ```ignore
+/// #![feature(try_trait_v2)]
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let i = fn_try! {
+///     as Option<u32>: "42".parse::<u32>().ok()? + 1
+///     => catch {
+///         None => 0u32
+///     }
+/// };
+/// assert_eq!(43, i);
+/// ```
+///
+/// # Mixing `Option` and `Result`
+///
+/// The closure wrapping the expression returns a `Result`, so the `?` operator can
+/// usually not be applied to an `Option` inside of the expression. If the expression
+/// is prefixed with `missing_to(<error>):`, every `?` in the expression is rewritten,
+/// so that a `None` is converted into the given error before being propagated.
+/// The error is converted into the error type of the catch block via `From`, just
+/// like any other error propagated by the `?` operator. `Result` values are passed
+/// through unchanged.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+///
+/// enum LookupErr {
+///     Missing,
+///     NotANumber
+/// }
+///
+/// impl From<ParseIntError> for LookupErr {
+///     fn from(_: ParseIntError) -> LookupErr {
+///         LookupErr::NotANumber
+///     }
+/// }
+///
+/// let entries = vec!["17", "4711"];
+/// let i = fn_try! {
+///     missing_to(LookupErr::Missing): entries.get(1)?.parse::<u32>()?
+///     => catch {
+///         LookupErr::Missing => 0u32,
+///         LookupErr::NotANumber => u32::MAX
+///     }
+/// };
+/// assert_eq!(4711, i);
+/// ```
+///
+/// The rewriting is done token by token, so very long expressions may hit the
+/// macro recursion limit. In this case the limit can be raised via the
+/// `#![recursion_limit]` crate attribute.
+///
+/// # Inline mode
+///
+/// If the expression is prefixed with `inline`, it is not wrapped into a closure, but
+/// matched in place. Therefore the arms of the catch block can use `continue`, `break`
+/// and `return` with their normal meaning, e.g. to retry inside of a loop. Since there
+/// is no closure to return from, the `?` operator cannot be used in the expression;
+/// the expression itself has to evaluate to a `Result` (e.g. by chaining the steps with
+/// `and_then`) and the catch block has to match on its error type, no conversion via
+/// `From` is applied.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+///
+/// let mut inputs = vec!["x", "y", "42"].into_iter();
+/// let i = loop {
+///     let input = match inputs.next() {
+///         Some(input) => input,
+///         None => break 0,
+///     };
+///     break fn_try! {
+///         inline input.parse::<u32>()
+///         => catch {
+///             ParseIntError { .. } => continue
+///         }
+///     };
+/// };
+/// assert_eq!(42, i);
+/// ```
+///
+/// # Async code
+///
+/// This crate does not provide a dedicated async form, since closures cannot contain
+/// `.await`. But the inline mode can be used inside of an `async` block or function:
+/// the expression and the arms of the catch block are then part of the surrounding
+/// `async` block and may use `.await`, e.g. to fetch a fallback value asynchronously.
+/// The fallback future is only created and polled if the error arm is taken.
+///
+/// ```edition2018
+/// # #[macro_use]
+/// # extern crate fn_block;
+/// # use std::future::Future;
+/// # use std::task::{Context, Poll, Waker};
+/// # fn block_on<F: Future>(f: F) -> F::Output {
+/// #     let mut f = Box::pin(f);
+/// #     match f.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+/// #         Poll::Ready(v) => v,
+/// #         Poll::Pending => panic!("future not ready"),
+/// #     }
+/// # }
+/// use std::num::ParseIntError;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static FALLBACKS: AtomicUsize = AtomicUsize::new(0);
+///
+/// async fn cached_fallback() -> u32 {
+///     FALLBACKS.fetch_add(1, Ordering::SeqCst);
+///     42
+/// }
+///
+/// async fn lookup(input: &str) -> u32 {
+///     fn_try! {
+///         inline input.parse::<u32>()
+///         => catch {
+///             ParseIntError { .. } => cached_fallback().await
+///         }
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(42, block_on(lookup("x")));
+/// assert_eq!(1, FALLBACKS.load(Ordering::SeqCst));
+/// assert_eq!(7, block_on(lookup("7")));
+/// assert_eq!(1, FALLBACKS.load(Ordering::SeqCst));
+/// # }
+/// ```
+///
+/// # Timeout
+///
+/// A blocking expression can be bounded in time by prefixing it with
+/// `timeout(<duration>):`. The expression is then evaluated on a newly spawned helper
+/// thread and the calling thread waits at most the given [`Duration`] for its result.
+/// If the deadline passes, a [`Caught::Timeout`] is synthesized and routed into the
+/// catch block, which therefore has to start with a dedicated `timeout => <expr>` arm.
+/// All other arms handle errors raised in the expression, as usual. If the expression
+/// panics, the panic is propagated to the calling thread.
+///
+/// Since the expression runs on another thread, it is moved into the helper thread,
+/// so all captured values, the result value and the error type have to be `Send`
+/// and `'static`. These bounds are enforced by the expansion. On timeout the helper
+/// thread is not stopped; it keeps running in the background and its result is discarded.
+/// This form is only available if the crate feature "std" is enabled, otherwise it is
+/// rejected with a compile error.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// # #[cfg(feature = "std")] {
+/// use std::num::ParseIntError;
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// let i = fn_try! {
+///     timeout(Duration::from_millis(10)):
+///     {
+///         sleep(Duration::from_millis(500));
+///         "42".parse::<u32>()?
+///     }
+///     => catch {
+///         timeout => 0u32,
+///         ParseIntError { .. } => u32::MAX
+///     }
+/// };
+/// assert_eq!(0, i);
+/// # }
+/// ```
+///
+/// # Note of Caution
+///
+/// Note that this API may be subject of change! The names may change, and the
+/// automatic wrapping of result value may disappear. This functionality may be
+/// controversial and feedback is welcome if this functionality should stay.
+///
+/// # Internal workings
+///
+/// The returned `Result` from the closure will be matched. If an `Ok` is
+/// wrapped return value will be returned from the `fn_try`. If the returned
+/// result wrapps an error, the error type must be handled by the `=> catch` block
+/// Following the expression given by the user. This is basically a match block where
+/// the user has to define recovery cases matching error types to the success return type.
+/// The patterns of the catch block are spliced into the match on the `Result` as
+/// `Err(<pattern>)` arms, so the expansion is a single match, just like a hand-written one.
+///
+/// # Design descisions
+///
+/// The name of the macro and the `=> catch` block are chosen to be similar to the ones
+/// chosen for [RFC 2388] and should still work with the "Rust 2018 Edition".
+/// Unfortunately the macro name may confuse users of the deprecated `try!`
+/// macro, but it looks similar to `try`/`catch` blocks in other languages. The automatic
+/// wrapping of the sucessful result value into a `Result::Ok` may also be controversial
+/// and even the author is not entirely sure if this is the best way to model the API.
+/// However, [RFC 2388] already seems to settle on the automatic wrapping and the resulting
+/// code may look more familiar to people comming from other languages.
+///
+/// [RFC 2388]: https://rust-lang.github.io/rfcs/2388-try-expr.html
+/// [`failure`]: https://crates.io/crates/failure
+/// [`tracing`]: https://crates.io/crates/tracing
+/// [`Duration`]: https://doc.rust-lang.org/std/time/struct.Duration.html
+/// [`Caught::Timeout`]: enum.Caught.html#variant.Timeout
+/// [`CaughtBacktrace`]: type.CaughtBacktrace.html
+#[macro_export]
+#[cfg(feature = "unproven")]
+macro_rules! fn_try {
+	(span $name:literal: $($rest:tt)+) => {
+		$crate::__fn_try_span!($name: $($rest)+)
+	};
+	(as $try_type:ty: $($rest:tt)+) => {
+		$crate::__fn_try_trait!($try_type: $($rest)+)
+	};
+	(missing_to($missing:expr): $($rest:tt)+) => {
+		$crate::__rewrite_missing!(@split ($missing) [] $($rest)+)
+	};
+	(inline $body:expr => catch {
+		$($err_pat:pat => $pat_bod:expr),+
+	}) => {
+		match $body {
+			Ok(v) => v,
+			$(Err($err_pat) => $pat_bod),+
+		}
+	};
+	(timeout($timeout:expr): $($rest:tt)+) => {
+		$crate::__fn_try_timeout!(($timeout) $($rest)+)
+	};
+	({ $($body:tt)* } => catch {
+		$($err_pat:pat => $pat_bod:expr),+
+	}) => {
+		match (|| { Ok({ $($body)* }) })() {
+			Ok(v) => v,
+			$(Err($err_pat) => $pat_bod),+
+		}
+	};
+	($body:expr => catch {
+		$($err_pat:pat => $pat_bod:expr),+
+	}) => {
+		match (|| { Ok($body) })() {
+			Ok(v) => v,
+			$(Err($err_pat) => $pat_bod),+
+		}
+	};
+	($body:expr => catch {
+		$($err_pat:pat => $pat_bod:expr),+
+	} on_error $hook:expr) => {
+		match (|| { Ok($body) })() {
+			Ok(v) => v,
+			Err(e) => {
+				$crate::__private::on_error(&e, $hook);
+				match e {
+					$($err_pat => $pat_bod),+
+				}
+			}
+		}
+	};
+	($body:expr => catch ($err:pat, $trace:pat) $handler:block) => {
+		match (|| { Ok($body) })() {
+			Ok(v) => v,
+			Err(e) => {
+				let ($err, $trace) = (e, $crate::__private::capture_backtrace());
+				$handler
+			}
+		}
+	};
+	($body:expr => catch display $handler:expr) => {
+		match (|| -> Result<_, $crate::__private::DisplayedError> { Ok($body) })() {
+			Ok(v) => v,
+			Err(e) => e.handle($handler),
+		}
+	};
+	($body:expr => $(catch $err_ty:ty as $err:ident $handler:block)+) => {
+		$crate::__fn_try_typed!(@variants ($body) [] [C0 C1 C2 C3 C4 C5 C6 C7]
+			$(($err_ty, $err, $handler))+)
+	};
+	($body:expr => collect_errors into $sink:expr; else $default:expr) => {
+		match (|| { Ok($body) })() {
+			Ok(v) => v,
+			Err(e) => {
+				$sink.push(e);
+				$default
+			}
+		}
+	};
+	($body:expr => catch $handler:expr) => {
+		(|| { Ok($body) })().unwrap_or_else($handler)
+	};
+}
+
+/// Internal helper of [`fn_try!`], implementing the `span "<name>":` form
+/// if the crate feature `tracing` is enabled.
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "unproven", feature = "tracing"))]
+macro_rules! __fn_try_span {
+	($name:literal: $body:expr => catch {
+		$($err_pat:pat => $pat_bod:expr),+
+	}) => {{
+		let span = $crate::__private::tracing::info_span!($name);
+		match span.in_scope(|| Ok($body)) {
+			Ok(v) => v,
+			Err(e) => {
+				span.in_scope(|| $crate::__private::tracing::error!(error = ?e, "recovering from error"));
+				match e {
+					$($err_pat => $pat_bod),+
+				}
+			}
+		}
+	}};
+}
+
+/// Internal helper of [`fn_try!`], rejecting the `span "<name>":` form
+/// if the crate feature `tracing` is not enabled.
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "unproven", not(feature = "tracing")))]
+macro_rules! __fn_try_span {
+	($($input:tt)*) => {
+		compile_error!("the `span` form of `fn_try!` requires the crate feature \"tracing\"")
+	};
+}
+
+/// Internal helper of [`fn_try!`], implementing the `timeout(<duration>):` form
+/// if the crate feature `std` is enabled.
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "unproven", feature = "std"))]
+macro_rules! __fn_try_timeout {
+	(($timeout:expr) $body:expr => catch {
+		timeout => $timeout_bod:expr,
+		$($err_pat:pat => $pat_bod:expr),+
+	}) => {
+		match $crate::__private::run_with_timeout($timeout, move || { Ok($body) }) {
+			Ok(v) => v,
+			Err($crate::Caught::Timeout) => $timeout_bod,
+			$(Err($crate::Caught::Error($err_pat)) => $pat_bod),+
+		}
+	};
+}
+
+/// Internal helper of [`fn_try!`], rejecting the `timeout(<duration>):` form
+/// if the crate feature `std` is not enabled.
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "unproven", not(feature = "std")))]
+macro_rules! __fn_try_timeout {
+	($($input:tt)*) => {
+		compile_error!("the `timeout` form of `fn_try!` requires the crate feature \"std\"")
+	};
+}
+
+/// Internal helper of [`fn_try!`], implementing the `as <Type>:` form
+/// if the crate feature `nightly-try-trait` is enabled.
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "unproven", feature = "nightly-try-trait"))]
+macro_rules! __fn_try_trait {
+	($try_type:ty: $body:expr => catch {
+		$($residual_pat:pat => $pat_bod:expr),+
+	}) => {
+		match $crate::__private::branch((|| -> $try_type { $crate::__private::from_output($body) })()) {
+			$crate::__private::ControlFlow::Continue(v) => v,
+			$($crate::__private::ControlFlow::Break($residual_pat) => $pat_bod),+
+		}
+	};
+}
+
+/// Internal helper of [`fn_try!`], rejecting the `as <Type>:` form
+/// if the crate feature `nightly-try-trait` is not enabled.
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "unproven", not(feature = "nightly-try-trait")))]
+macro_rules! __fn_try_trait {
+	($($input:tt)*) => {
+		compile_error!("the `as <Type>:` form of `fn_try!` requires the crate feature \"nightly-try-trait\"")
+	};
+}
+
+/// Internal helper of [`fn_try!`], assigning a variant name of the generated error enum
+/// to each of the typed catch sections and emitting the enum, its `From` implementations
+/// and the match dispatching the errors to the catch sections.
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "unproven")]
+macro_rules! __fn_try_typed {
+	(@variants $body:tt [$($done:tt)*] [$var:ident $($vars:ident)*]
+		($err_ty:ty, $err:ident, $handler:block) $($rest:tt)*) => {
+		$crate::__fn_try_typed!(@variants $body [$($done)* ($var, $err_ty, $err, $handler)] [$($vars)*]
+			$($rest)*)
+	};
+	(@variants $body:tt $done:tt [] $($rest:tt)+) => {
+		compile_error!("fn_try! supports at most eight typed catch sections")
+	};
+	(@variants ($body:expr) [$(($var:ident, $err_ty:ty, $err:ident, $handler:block))+] $vars:tt) => {{
+		enum FnTryCaught {
+			$($var($err_ty)),+
+		}
+		$(
+			impl From<$err_ty> for FnTryCaught {
+				fn from(e: $err_ty) -> FnTryCaught {
+					FnTryCaught::$var(e)
+				}
+			}
+		)+
+		match (|| -> Result<_, FnTryCaught> { Ok($body) })() {
+			Ok(v) => v,
+			$(Err(FnTryCaught::$var($err)) => $handler),+
+		}
+	}};
+}
+
+/// Internal helper of [`fn_try!`] and [`fn_expr_res!`], rewriting every `?` in the
+/// expression given to the `missing_to(<error>):` form of `fn_try!`, or to `fn_expr_res!`.
+/// For `fn_try!` the input is first split at the `=> catch` token. The expression tokens
+/// are walked, descending into delimited groups via an explicit stack of the surrounding
+/// tokens. The rewritten expression is finally passed on to the macro named by the
+/// second argument, `(catch ...)` for `fn_try!` and `(result ...)` for `fn_expr_res!`.
+///
+/// [`fn_try!`]: macro.fn_try.html
+/// [`fn_expr_res!`]: macro.fn_expr_res.html
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __rewrite_missing {
+	(@split $missing:tt [$($body:tt)*] => catch $catch:tt) => {
+		$crate::__rewrite_missing!(@rewrite $missing (catch $catch) [] [] $($body)*)
+	};
+	(@split $missing:tt [$($body:tt)*] $next:tt $($rest:tt)*) => {
+		$crate::__rewrite_missing!(@split $missing [$($body)* $next] $($rest)*)
+	};
+	(@rewrite ($missing:expr) $catch:tt [$($out:tt)*] $stack:tt ? $($rest:tt)*) => {
+		$crate::__rewrite_missing!(@rewrite ($missing) $catch
+			[$($out)* .none_to_err(|| $missing)?] $stack $($rest)*)
+	};
+	(@rewrite $missing:tt $catch:tt [$($out:tt)*] [$($stack:tt)*] ($($inner:tt)*) $($rest:tt)*) => {
+		$crate::__rewrite_missing!(@rewrite $missing $catch
+			[] [(paren [$($out)*] [$($rest)*]) $($stack)*] $($inner)*)
+	};
+	(@rewrite $missing:tt $catch:tt [$($out:tt)*] [$($stack:tt)*] [$($inner:tt)*] $($rest:tt)*) => {
+		$crate::__rewrite_missing!(@rewrite $missing $catch
+			[] [(bracket [$($out)*] [$($rest)*]) $($stack)*] $($inner)*)
+	};
+	(@rewrite $missing:tt $catch:tt [$($out:tt)*] [$($stack:tt)*] {$($inner:tt)*} $($rest:tt)*) => {
+		$crate::__rewrite_missing!(@rewrite $missing $catch
+			[] [(brace [$($out)*] [$($rest)*]) $($stack)*] $($inner)*)
+	};
+	(@rewrite $missing:tt $catch:tt [$($out:tt)*] $stack:tt $next:tt $($rest:tt)*) => {
+		$crate::__rewrite_missing!(@rewrite $missing $catch [$($out)* $next] $stack $($rest)*)
+	};
+	(@rewrite $missing:tt $catch:tt [$($inner:tt)*]
+		[(paren [$($out:tt)*] [$($rest:tt)*]) $($stack:tt)*]) => {
+		$crate::__rewrite_missing!(@rewrite $missing $catch [$($out)* ($($inner)*)] [$($stack)*] $($rest)*)
+	};
+	(@rewrite $missing:tt $catch:tt [$($inner:tt)*]
+		[(bracket [$($out:tt)*] [$($rest:tt)*]) $($stack:tt)*]) => {
+		$crate::__rewrite_missing!(@rewrite $missing $catch [$($out)* [$($inner)*]] [$($stack)*] $($rest)*)
+	};
+	(@rewrite $missing:tt $catch:tt [$($inner:tt)*]
+		[(brace [$($out:tt)*] [$($rest:tt)*]) $($stack:tt)*]) => {
+		$crate::__rewrite_missing!(@rewrite $missing $catch [$($out)* {$($inner)*}] [$($stack)*] $($rest)*)
+	};
+	(@rewrite $missing:tt (catch $catch:tt) [$($body:tt)*] []) => {
+		$crate::fn_try!({
+			#[allow(unused_imports)]
+			use $crate::OptionToResult;
+			$($body)*
+		} => catch $catch)
+	};
+	(@rewrite $missing:tt (result $err:ty) [$($body:tt)*] []) => {
+		(|| -> Result<_, $err> {
+			#[allow(unused_imports)]
+			use $crate::OptionToResult;
+			let v = { $($body)* };
+			Ok(v)
+		})()
+	};
+}
+
+/// This macro evaluates multiple fallible expressions with one shared catch block.
+/// Each expression given in the brackets has to evaluate to a `Result`. The expressions
+/// are evaluated from left to right inside of a closure, like the expression of
+/// [`fn_try!`], and the macro evaluates to a tuple of all success values. On the first
+/// expression evaluating to an `Err`, the remaining expressions are not evaluated and
+/// the error is converted into the error type of the catch block via `From`, so the
+/// catch block can tell which expression failed by the error variant. At least two
+/// expressions have to be given.
+/// - *Note*: This macro is an unstable API to make use of it, enable the crate feature "unproven".
+///
+/// # Example:
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+///
+/// let (host, port) = fn_try_all! {
+///     [Ok::<_, ParseIntError>("localhost"), "x".parse::<u16>()]
+///     => catch {
+///         ParseIntError { .. } => ("localhost", 80)
+///     }
+/// };
+/// assert_eq!(("localhost", 80), (host, port));
+/// ```
+///
+/// # Aggregating errors
+///
+/// If the expressions are given without brackets, each followed by a `?`, and the catch
+/// block consists of a single arm of the form `<name>: Vec<ErrorType> => <handler>`,
+/// the expressions are evaluated independently of each other: a failing expression
+/// does not prevent the evaluation of the following expressions. The errors of all
+/// failing expressions are converted into the given error type via `From` and are
+/// collected in expression order into a `Vec`, which is bound to the given name
+/// for the handler. This is useful for validations, which should report all problems
+/// at once.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+///
+/// let (width, height, depth) = fn_try_all! {
+///     "x".parse::<u32>()?, "20".parse::<u32>()?, "-1".parse::<u32>()?
+///     => catch {
+///         errors: Vec<ParseIntError> => {
+///             assert_eq!(2, errors.len());
+///             (0, 0, 0)
+///         }
+///     }
+/// };
+/// assert_eq!((0, 0, 0), (width, height, depth));
+/// ```
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[macro_export]
+#[cfg(feature = "unproven")]
+macro_rules! fn_try_all {
+	([$first:expr, $($rest:expr),+ $(,)*] => catch {
+		$($err_pat:pat => $pat_bod:expr),+
+	}) => {
+		match (|| { Ok(($first?, $($rest?),+)) })() {
+			Ok(v) => v,
+			$(Err($err_pat) => $pat_bod),+
+		}
+	};
+	($first:expr, $($rest:expr),+ => catch {
+		$errors:ident : Vec<$err:ty> => $handler:expr $(,)*
+	}) => {{
+		let mut errors = Vec::new();
+		let values = (
+			$crate::__fn_try_all_step!(errors, $err, $first),
+			$($crate::__fn_try_all_step!(errors, $err, $rest)),+
+		);
+		match $crate::__private::AllSome::all_some(values) {
+			Some(v) => v,
+			None => {
+				let $errors: Vec<$err> = errors;
+				$handler
+			}
+		}
+	}};
+}
+
+/// Internal helper of [`fn_try_all!`], evaluating one step of the aggregating form
+/// into an `Option` and pushing the error of a failing step to the given errors.
+///
+/// [`fn_try_all!`]: macro.fn_try_all.html
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "unproven")]
+macro_rules! __fn_try_all_step {
+	($errors:ident, $err:ty, $step:expr) => {
+		match (|| -> Result<_, $err> { Ok($step) })() {
+			Ok(v) => Some(v),
+			Err(e) => {
+				$errors.push(e);
+				None
+			}
+		}
+	};
+}
+
+/// This macro wraps the given block into a coroutine closure (a "generator") and
+/// returns an iterator over the values yielded by it. This allows navigation logic
+/// to lazily produce a sequence of values using `yield` statements, without having
+/// to implement `Iterator` by hand. The closure is a `move` closure, so captured
+/// values are moved into the returned iterator, which can therefore be returned
+/// from a function. The block has to evaluate to `()`.
+/// - *Note*: This macro is an unstable API to make use of it, enable the crate features
+///   "unproven" and "nightly-coroutines". The latter requires a nightly compiler and
+///   the crate using the macro has to enable the language feature `coroutines`.
+///
+/// # Example:
+///
+/// ```ignore
+/// #![feature(coroutines)]
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let names = vec!["foo", "", "bar"];
+/// let upper = fn_gen!{
+///     for name in names {
+///         if !name.is_empty() {
+///             yield name.to_uppercase();
+///         }
+///     }
+/// };
+/// assert_eq!(vec!["FOO", "BAR"], upper.collect::<Vec<_>>());
+/// ```
+#[macro_export]
+#[cfg(all(feature = "unproven", feature = "nightly-coroutines"))]
+macro_rules! fn_gen {
+	($($body:tt)*) => {
+		$crate::__private::gen_iter(#[coroutine] move || { $($body)* })
+	};
+}
+
+/// This macro wraps the body of a `main` function like [`fn_try!`] and converts
+/// the outcome into a `std::process::ExitCode`, so it composes with `fn main() -> ExitCode`.
+/// The result value of the body and the values of the catch arms are converted into an
+/// exit code: `()` maps to `ExitCode::SUCCESS`, a `u8` or `i32` is used as exit code
+/// (an `i32` outside of the range `0..=255` maps to `ExitCode::FAILURE`) and an `ExitCode`
+/// is passed through. The catch arms are typically used to print the error and
+/// select an exit code.
+/// - *Note*: This macro is an unstable API to make use of it, enable the crate feature "unproven".
+/// - *Note*: This macro is only available if the crate feature "std" is enabled, which it is by default.
+///
+/// # Example:
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+/// use std::process::ExitCode;
+///
+/// fn main() -> ExitCode {
+///     fn_main_try! {
+///         {
+///             let port = "8080".parse::<u16>()?;
+///             println!("listening on port {}", port);
+///         }
+///         => catch {
+///             e @ ParseIntError { .. } => {
+///                 eprintln!("invalid port: {}", e);
+///                 2
+///             }
+///         }
+///     }
+/// }
+/// ```
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[macro_export]
+#[cfg(all(feature = "unproven", feature = "std"))]
+macro_rules! fn_main_try {
+	($body:expr => catch {
+		$($err_pat:pat => $pat_bod:expr),+
+	}) => {
+		match (|| { Ok($body) })() {
+			Ok(v) => $crate::__private::ExitCodeValue::exit_code(v),
+			$(Err($err_pat) => $crate::__private::ExitCodeValue::exit_code($pat_bod)),+
+		}
+	};
+}
+
+/// This macro works like [`fn_try!`] and accepts all of its forms, but wraps the
+/// resulting value, be it the value of the expression or the recovered value, into a
+/// `Result::Ok`. This bridges the recovery with APIs demanding a `Result`, e.g. a function
+/// whose signature returns a `Result`. The error type of the resulting `Result` is inferred
+/// from the context; without context it can be pinned, e.g. to `Infallible`.
+/// - *Note*: This macro is an unstable API to make use of it, enable the crate feature "unproven".
+///
+/// # Example:
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::convert::Infallible;
+/// use std::num::ParseIntError;
+///
+/// let res: Result<u32, Infallible> = fn_try_keep_result! {
+///     "x".parse::<u32>()?
+///     => catch {
+///         ParseIntError { .. } => 0u32
+///     }
+/// };
+/// assert_eq!(Ok(0), res);
+/// ```
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[macro_export]
+#[cfg(feature = "unproven")]
+macro_rules! fn_try_keep_result {
+	($($input:tt)+) => {
+		Ok($crate::fn_try!{ $($input)+ })
+	};
+}
+
+/// This macro works like [`fn_try!`], but evaluates to a tuple of the result
+/// value and an `Option` holding the error the value was recovered from.
+/// If the expression succeeds, the tuple holds the result value and `None`.
+/// If an error was raised in the expression, the tuple holds the value produced by
+/// the `=> catch` block and the error wrapped in a `Some`. This allows callers to
+/// detect that a recovery happened, e.g. for diagnostics, without changing the
+/// recovered value.
+/// - *Note*: This macro is an unstable API to make use of it, enable the crate feature "unproven".
+///
+/// Since the error is returned, the catch block matches on a *reference* to the error.
+/// Thanks to default binding modes, patterns can be written just like in [`fn_try!`],
+/// but bindings in the patterns bind references to parts of the error.
+///
+/// # Example:
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum ConvertErr {
+///     IntParseErr
+/// }
+///
+/// impl From<ParseIntError> for ConvertErr {
+///     fn from(_: ParseIntError) -> ConvertErr {
+///         ConvertErr::IntParseErr
+///     }
+/// }
+///
+/// let (i, err) = fn_try_logged! {
+///     "x".parse::<u32>()?
+///     => catch {
+///         ConvertErr::IntParseErr => 0u32
+///     }
+/// };
+/// assert_eq!(0, i);
+/// assert_eq!(Some(ConvertErr::IntParseErr), err);
+/// ```
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[macro_export]
+#[cfg(feature = "unproven")]
+macro_rules! fn_try_logged {
+	($body:expr => catch {
+		$($err_pat:pat => $pat_bod:expr),+
+	}) => {
+		match (|| { Ok($body) })() {
+			Ok(v) => (v, None),
+			Err(e) => {
+				let v = match &e {
+					$($err_pat => $pat_bod),+
+				};
+				(v, Some(e))
+			}
+		}
+	};
+}
+
+/// This macro wraps a given rust code expression into a closure and
+/// directly calls the closure, like [`fn_try!`]. The result value of the expression is
+/// automatically wrapped into a `Result::Ok`, but instead of recovering from errors in
+/// a catch block, the macro evaluates to an `Option`: `Some` holding the result value
+/// if the expression succeeded, and `None` if any error was raised in the expression.
+/// - *Note*: This macro is an unstable API to make use of it, enable the crate feature "unproven".
+///
+/// # Example:
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::str::from_utf8;
+///
+/// let s: &[u8] = &[0x0020, 0x0034, 0x0032];
+/// let i = fn_try_opt!{ from_utf8(s)?.trim().parse::<u32>()? };
+/// assert_eq!(Some(42), i);
+///
+/// let s: &[u8] = &[0x0020, 0x005A, 0x0032];
+/// let i = fn_try_opt!{ from_utf8(s)?.trim().parse::<u32>()? };
+/// assert_eq!(None, i);
+/// ```
+/// Since the errors are discarded, they are converted into an internal error type
+/// which requires the errors to implement `Debug`. If the expression relies on the
+/// `?` operator converting errors into a specific error type, the type can be pinned
+/// by prefixing the expression with `as <ErrorType>:`.
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// struct ConvertErr();
+/// impl From<std::num::ParseIntError> for ConvertErr {
+///     fn from(_: std::num::ParseIntError) -> ConvertErr {ConvertErr()}
+/// }
+/// fn parse(s: &str) -> Result<u32, ConvertErr> {
+///     Ok(s.trim().parse::<u32>()?)
+/// }
+/// let i = fn_try_opt!{ as ConvertErr: parse(" 42")? };
+/// assert_eq!(Some(42), i);
+/// ```
+///
+/// # Logging
+///
+/// If the crate feature `log` is enabled, the expression (or the `as <ErrorType>:` prefix)
+/// can be prefixed with `log`, to record each discarded error with its `Debug`
+/// representation as a warning via the [`log`] crate, e.g. `fn_try_opt!{ log: expr }` or
+/// `fn_try_opt!{ log as ConvertErr: expr }`. With the `as <ErrorType>:` prefix the error
+/// type has to implement `Debug`. Without the `log` feature, the `log` modifier is rejected
+/// with a compile error.
+///
+/// [`fn_try!`]: macro.fn_try.html
+/// [`log`]: https://crates.io/crates/log
+#[macro_export]
+#[cfg(feature = "unproven")]
+macro_rules! fn_try_opt {
+	(log as $err_type:ty: $body:expr) => {
+		$crate::__fn_try_opt_log!($err_type, "{:?}": $body)
+	};
+	(log: $body:expr) => {
+		$crate::__fn_try_opt_log!($crate::__private::DebuggedError, "{}": $body)
+	};
+	(as $err_type:ty: $body:expr) => {
+		(|| -> Result<_, $err_type> { Ok($body) })().ok()
+	};
+	($body:expr) => {
+		(|| -> Result<_, $crate::__private::DiscardedError> { Ok($body) })().ok()
+	};
+}
+
+/// Internal helper of [`fn_try_opt!`], implementing the `log` modifier
+/// if the crate feature `log` is enabled.
+///
+/// [`fn_try_opt!`]: macro.fn_try_opt.html
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "unproven", feature = "log"))]
+macro_rules! __fn_try_opt_log {
+	($err_type:ty, $err_format:tt: $body:expr) => {
+		match (|| -> Result<_, $err_type> { Ok($body) })() {
+			Ok(v) => Some(v),
+			Err(e) => {
+				$crate::__private::log::warn!(concat!("fn_try_opt! discarded error: ", $err_format), e);
+				None
+			}
+		}
+	};
+}
+
+/// Internal helper of [`fn_try_opt!`], rejecting the `log` modifier
+/// if the crate feature `log` is not enabled.
+///
+/// [`fn_try_opt!`]: macro.fn_try_opt.html
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "unproven", not(feature = "log")))]
+macro_rules! __fn_try_opt_log {
+	($($input:tt)*) => {
+		compile_error!("the `log` modifier of `fn_try_opt!` requires the crate feature \"log\"")
+	};
+}
+
+/// This macro wraps a given rust code expression into a closure and
+/// directly calls the closure, like [`fn_try!`]. If any error was raised in the
+/// expression, the macro evaluates to the given default expression instead of
+/// recovering in a catch block. The default expression is only evaluated if an
+/// error was raised. This is the simplest form of recovery, covering the case
+/// where the kind of error does not matter.
+/// - *Note*: This macro is an unstable API to make use of it, enable the crate feature "unproven".
+///
+/// # Example:
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let i = fn_try_or!{ "x".parse::<u32>()?, 0 };
+/// assert_eq!(0, i);
+/// let i = fn_try_or!{ "42".parse::<u32>()?, 0 };
+/// assert_eq!(42, i);
+/// ```
+/// Since the errors are discarded, they are converted into an internal error type,
+/// so the error type does not need to be inferred from the default expression.
+///
+/// # Restriction
+///
+/// The internal error type can only be constructed from errors implementing `Debug`.
+/// Applying the `?` operator to a `Result` whose error type does not implement `Debug`
+/// fails to compile with an error about a missing `From` implementation for the
+/// internal error type. Such errors can be discarded explicitly before applying
+/// the `?` operator, e.g. via `.map_err(drop)?`:
+///
+/// ```
+/// # #[macro_use]
+/// # use fn_block::*;
+/// struct NoDebug;
+/// fn check(i: u32) -> Result<u32, NoDebug> {
+///     if i > 10 { Ok(i) } else { Err(NoDebug) }
+/// }
+/// let i = fn_try_or!{ check("5".parse::<u32>()?).map_err(drop)?, 10 };
+/// assert_eq!(10, i);
+/// ```
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[macro_export]
+#[cfg(feature = "unproven")]
+macro_rules! fn_try_or {
+	($body:expr, $default:expr) => {
+		match (|| -> Result<_, $crate::__private::DiscardedError> { Ok($body) })() {
+			Ok(v) => v,
+			Err(_) => $default,
+		}
+	};
+}
+
+/// This macro wraps a given rust code expression into a closure and
+/// directly calls the closure, like [`fn_try!`]. The result value of the expression is
+/// automatically wrapped into a `Result::Ok`, but instead of recovering from errors in
+/// a catch block, the macro panics if any error was raised in the expression.
+/// The panic message consists of the given message, followed by the `Debug`
+/// representation of the error. The message is a format string, which may refer to
+/// variables in scope, just like the format string of `format!`. This is useful in
+/// tests and prototypes, where failure should be reported with some context.
+/// - *Note*: This macro is an unstable API to make use of it, enable the crate feature "unproven".
+///
+/// # Example:
+/// ```should_panic
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let path = "config.toml";
+/// let content = "timeout = x";
+/// // panics with: parsing config config.toml: ParseIntError { kind: InvalidDigit }
+/// let timeout = fn_try_expect!{ "parsing config {path}":
+///     content.split('=').nth(1).unwrap_or("").trim().parse::<u32>()?
+/// };
+/// ```
+/// Since the errors are only formatted, they are converted into an internal error type
+/// which requires the errors to implement `Debug`.
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[macro_export]
+#[cfg(feature = "unproven")]
+macro_rules! fn_try_expect {
+	($msg:literal: $body:expr) => {
+		match (|| -> Result<_, $crate::__private::DebuggedError> { Ok($body) })() {
+			Ok(v) => v,
+			Err(e) => panic!("{}: {}", format_args!($msg), e),
+		}
+	};
+}
+
+///////////////////////
+// Trait definitions //
+///////////////////////
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_some`, which moves the
+/// value on which it is called into an `Optional::Some`.
+/// This is particularly useful when having to wrap a value into
+/// a `Some` at the end of a call chain.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let o : Option<String> = "foo bar ".trim().to_uppercase().into_some();
+/// assert_eq!("FOO BAR", o.unwrap());
+/// ```
+///
+/// Note that `Cow<'a, B>` is always sized, even if the borrowed type `B` is unsized,
+/// like `str` or `[T]`, so both borrowed and owned `Cow` values can be wrapped
+/// into a `Some`.
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::borrow::Cow;
+///
+/// let o : Option<Cow<str>> = Cow::Borrowed("foo").into_some();
+/// assert_eq!(Some(Cow::Borrowed("foo")), o);
+/// ```
+///
+/// # Deriving
+///
+/// If the crate feature `derive` is enabled, `#[derive(IntoSome)]` generates an inherent
+/// method `into_some` for a type, which takes precedence over the method of this trait
+/// in method call syntax. With the attribute `#[into_some(none_if = "<expr>")]`, the
+/// generated method returns `None` if the given expression evaluates to `true`, which
+/// allows mapping sentinel values to `None`. Since this trait is implemented for all
+/// sized types, generic code bound on `IntoSome` still uses the blanket implementation.
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// # #[cfg(feature = "derive")] {
+/// #[derive(Debug, PartialEq, IntoSome)]
+/// #[into_some(none_if = "self.0 == 0")]
+/// struct UserId(u32);
+///
+/// let ids = Some("0");
+/// let id = fn_expr!{ { UserId(ids?.parse().ok()?) }.into_some() };
+/// assert_eq!(None, id);
+/// # }
+/// ```
+///
+/// This can e.g. be used inside of
+/// an expression wrapped in a [`fn_expr!`] or [`fn_block!`] macro.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let o = Some("Foobar");
+/// let s = fn_expr!{ o?.get(0..3)?.to_lowercase().into_some() };
+/// assert_eq!("foo", s.unwrap());
+/// ```
+///
+/// [`fn_expr!`]: macro.fn_expr.html
+/// [`fn_block!`]: macro.fn_block.html
+pub trait IntoSome: Sized {
+    fn into_some(self) -> Option<Self>;
+
+    /// This method calls `f` with `self`, returning the result of `f`.
+    /// It is equivalent to `self.into_some().and_then(f)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use fn_block::IntoSome;
+    /// let half = 42.into_some_then(|i: u32| i.checked_div(2));
+    /// assert_eq!(Some(21), half);
+    /// ```
+    fn into_some_then<U, F>(self, f: F) -> Option<U>
+    where
+        F: FnOnce(Self) -> Option<U>;
+
+    /// This method pairs `self` with `other` and wraps the pair into a `Some`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use fn_block::IntoSome;
+    /// assert_eq!(Some(("localhost", 8080)), "localhost".into_some_zip(8080));
+    /// ```
+    fn into_some_zip<B>(self, other: B) -> Option<(Self, B)>;
+
+    /// This method pairs `self` with the value wrapped in `other` and wraps the
+    /// pair into a `Some`, if `other` is a `Some`. Otherwise `None` is returned.
+    /// It is equivalent to `self.into_some().zip(other)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use fn_block::IntoSome;
+    /// let port = "8080".parse::<u16>().ok();
+    /// assert_eq!(Some(("localhost", 8080)), "localhost".into_some_zip_opt(port));
+    /// assert_eq!(None, "localhost".into_some_zip_opt(None::<u16>));
+    /// ```
+    fn into_some_zip_opt<B>(self, other: Option<B>) -> Option<(Self, B)>;
+}
+
+/// Implementration of trait `IntoSome` for
+/// all sized types.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::IntoSome;
+/// let five = 5.into_some();
+/// assert_eq!(Some(5), five);
+/// ```
+///
+impl<T> IntoSome for T {
+    /// This method moves `self` into a `Some` and returns it.
+    #[inline(always)]
+    fn into_some(self) -> Option<Self> {
+        Some(self)
+    }
+
+    #[inline(always)]
+    fn into_some_then<U, F>(self, f: F) -> Option<U>
+    where
+        F: FnOnce(Self) -> Option<U>,
+    {
+        f(self)
+    }
+
+    #[inline(always)]
+    fn into_some_zip<B>(self, other: B) -> Option<(Self, B)> {
+        Some((self, other))
+    }
+
+    #[inline(always)]
+    fn into_some_zip_opt<B>(self, other: Option<B>) -> Option<(Self, B)> {
+        other.map(|b| (self, b))
+    }
+}
+
+/// This trait, which is implemented for all types, including unsized ones,
+/// provides the method `into_some_ref`, which wraps a reference to the
+/// value on which it is called into a `Some`. Other than [`into_some`], the
+/// value is only borrowed, so it can be used at the end of a chain producing
+/// a reference without cloning the value. The returned `Option` borrows
+/// from the value it was called on.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::collections::HashMap;
+/// let mut names = HashMap::new();
+/// names.insert(42, "  Foo ".to_string());
+/// let name: Option<&str> = fn_expr!{ names.get(&42)?.trim().into_some_ref() };
+/// assert_eq!(Some("Foo"), name);
+/// ```
+///
+/// [`into_some`]: trait.IntoSome.html#tymethod.into_some
+pub trait IntoSomeRef {
+    /// This method wraps a reference to `self` into a `Some` and returns it.
+    #[allow(clippy::wrong_self_convention)]
+    fn into_some_ref(&self) -> Option<&Self>;
+}
+
+/// Implementation of trait `IntoSomeRef` for
+/// all types.
+impl<T: ?Sized> IntoSomeRef for T {
+    #[inline(always)]
+    fn into_some_ref(&self) -> Option<&Self> {
+        Some(self)
+    }
+}
+
+/// This trait, which is implemented for all types, including unsized ones,
+/// provides the method `into_some_mut`, which wraps a mutable reference to the
+/// value on which it is called into a `Some`. This is the mutable counterpart
+/// of [`IntoSomeRef`]. Since a mutable reference is not `Copy`, the value stays
+/// mutably borrowed as long as the returned `Option` is in use.
+///
+/// Note that a mutable reference can only be returned from a [`fn_expr!`]
+/// if the reference it is derived from is moved into the wrapped expression,
+/// e.g. by wrapping it into a block: `{ names }`. Otherwise the lambda
+/// generated by the macro only borrows the reference and cannot hand out
+/// a reborrow of it.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::collections::HashMap;
+/// let mut map = HashMap::new();
+/// map.insert(42, "foo".to_string());
+/// let names = &mut map;
+/// if let Some(name) = fn_expr!{ { names }.get_mut(&42)?.into_some_mut() } {
+///     name.push_str("bar");
+/// }
+/// assert_eq!("foobar", map[&42]);
+/// ```
+///
+/// Only one of the returned references can be used at a time:
+///
+/// ```compile_fail
+/// # use fn_block::*;
+/// let mut name = "foo".to_string();
+/// let first = name.into_some_mut();
+/// let second = name.into_some_mut();
+/// first.unwrap().push_str("bar");
+/// second.unwrap().push_str("baz");
+/// ```
+///
+/// [`IntoSomeRef`]: trait.IntoSomeRef.html
+/// [`fn_expr!`]: macro.fn_expr.html
+pub trait IntoSomeMut {
+    /// This method wraps a mutable reference to `self` into a `Some` and returns it.
+    #[allow(clippy::wrong_self_convention)]
+    fn into_some_mut(&mut self) -> Option<&mut Self>;
+}
+
+/// Implementation of trait `IntoSomeMut` for
+/// all types.
+impl<T: ?Sized> IntoSomeMut for T {
+    #[inline(always)]
+    fn into_some_mut(&mut self) -> Option<&mut Self> {
+        Some(self)
+    }
+}
+
+/// This trait, which is implemented for mutable raw pointers,
+/// provides the unsafe method `into_some_non_null`, which wraps the pointer
+/// on which it is called into a `NonNull` inside of an `Option::Some`.
+/// Other than `NonNull::new`, the method does not check the pointer for null,
+/// which allows ending an unsafe call chain on a pointer which is known to be
+/// valid with a `Some`.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// let mut value = 42;
+/// let ptr = unsafe { (&mut value as *mut i32).into_some_non_null() };
+/// assert_eq!(42, unsafe { *ptr.unwrap().as_ptr() });
+/// ```
+///
+/// Since the method is unsafe, it cannot be called in safe code:
+///
+/// ```compile_fail
+/// # use fn_block::*;
+/// let mut value = 42;
+/// let ptr = (&mut value as *mut i32).into_some_non_null();
+/// ```
+pub trait IntoSomeNonNull<T: ?Sized> {
+    /// This method wraps `self` into a `NonNull` inside of a `Some` and returns it.
+    ///
+    /// # Safety
+    ///
+    /// The caller has to ensure that `self` is not null.
+    unsafe fn into_some_non_null(self) -> Option<NonNull<T>>;
+}
+
+/// Implementation of trait `IntoSomeNonNull` for
+/// all mutable raw pointers.
+impl<T: ?Sized> IntoSomeNonNull<T> for *mut T {
+    #[inline(always)]
+    unsafe fn into_some_non_null(self) -> Option<NonNull<T>> {
+        Some(NonNull::new_unchecked(self))
+    }
+}
+
+/// This trait, which is implemented for nested `Option` types,
+/// provides the method `into_some_flatten`, which removes one level of nesting.
+/// A nested `Option` typically results from wrapping a value into a `Some`
+/// via [`into_some`], which already is an `Option`. The method is equivalent
+/// to `Option::flatten`, but makes the intent explicit in the vocabulary of this crate.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// let first = "Foo".chars().next().into_some();
+/// assert_eq!(Some('F'), first.into_some_flatten());
+/// ```
+///
+/// [`into_some`]: trait.IntoSome.html#tymethod.into_some
+pub trait IntoSomeFlatten<T> {
+    /// This method returns the inner `Option`, or `None` if `self` is `None`.
+    fn into_some_flatten(self) -> Option<T>;
+}
+
+/// Implementation of trait `IntoSomeFlatten` for
+/// all `Option<Option<T>>` types.
+impl<T> IntoSomeFlatten<T> for Option<Option<T>> {
+    fn into_some_flatten(self) -> Option<T> {
+        self.flatten()
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_ok`, which moves the
+/// value on which it is called into an `Result::Ok`.
+/// This is particularly useful when having to wrap a value into
+/// an `Ok` at the end of a call chain.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let res : Result<String,()> = "foo bar ".trim().to_uppercase().into_ok();
+/// assert_eq!("FOO BAR", res.unwrap());
+/// ```
+///
+/// This can e.g. be used inside of
+/// an expression wrapped in a [`fn_expr!`] or [`fn_block!`] macro.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::str::from_utf8;
+/// use std::error::Error;
+/// struct ConvertErr();
+/// impl <T: Error> From<T> for ConvertErr {
+///     fn from(_: T) -> ConvertErr {ConvertErr()}
+/// }
+/// let s : &[u8] = &[0x0020,0x0034,0x0032];
+/// let res_int = fn_expr!{ Result<u32,ConvertErr>:
+///     from_utf8(s)?.trim().parse::<u32>()?.into_ok()
+/// }.unwrap_or(0u32);
+/// assert_eq!(res_int, 42);
+/// ```
+///
+/// # Deriving
+///
+/// If the crate feature `derive` is enabled, `#[derive(IntoOk)]` generates an inherent
+/// method `into_ok` for a type, which takes precedence over the method of this trait
+/// in method call syntax. The error type of the generated method is a type parameter
+/// of the method, so it can be given via turbofish. With the attribute
+/// `#[into_ok(error = "<type>")]`, the error type is fixed to the given type instead,
+/// which is useful for domain types that are mostly returned as `Ok` values.
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// # #[cfg(feature = "derive")] {
+/// #[derive(Debug)]
+/// struct ConfigErr;
+///
+/// #[derive(Debug, PartialEq, IntoOk)]
+/// #[into_ok(error = "ConfigErr")]
+/// struct Port(u16);
+///
+/// let port = fn_expr!{ { Port("8080".parse().map_err(|_| ConfigErr)?) }.into_ok() };
+/// assert_eq!(Port(8080), port.unwrap());
+/// # }
+/// ```
+///
+/// [`fn_expr!`]: macro.fn_expr.html
+/// [`fn_block!`]: macro.fn_block.html
+pub trait IntoOk<E>: Sized {
+    /// This method moves `self` into an `Ok` and returns it.
+    fn into_ok(self) -> Result<Self, E>;
+
+    /// This method calls `f` with `self`, returning the result of `f`.
+    /// It is equivalent to `self.into_ok().and_then(f)`,
+    /// e.g. for validating a value after wrapping it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use fn_block::IntoOk;
+    /// let validate = |port: u16| if port >= 1024 { Ok(port) } else { Err("privileged port") };
+    /// assert_eq!(Ok(8080), 8080.into_ok_then(validate));
+    /// assert_eq!(Err("privileged port"), 80.into_ok_then(validate));
+    /// ```
+    fn into_ok_then<U, F>(self, f: F) -> Result<U, E>
+    where
+        F: FnOnce(Self) -> Result<U, E>;
+}
+
+/// Implementration of trait `IntoOk` for
+/// all sized types.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::IntoOk;
+/// let five : Result<u32,()> = 5.into_ok();
+/// assert_eq!(Ok(5), five);
+/// ```
+///
 impl<T, E> IntoOk<E> for T {
+    #[inline(always)]
     fn into_ok(self) -> Result<Self, E> {
         Ok(self)
     }
+
+    #[inline(always)]
+    fn into_ok_then<U, F>(self, f: F) -> Result<U, E>
+    where
+        F: FnOnce(Self) -> Result<U, E>,
+    {
+        f(self)
+    }
+}
+
+/// This trait, which is implemented for all types, including unsized ones,
+/// provides the method `into_ok_ref`, which wraps a reference to the
+/// value on which it is called into a `Result::Ok`. This is the `Result`
+/// counterpart of [`IntoSomeRef`]: the value is only borrowed, so a chain
+/// producing a reference can end in a `Result` without cloning the value.
+/// The error type is left to inference. The returned `Result` borrows from
+/// the value it was called on.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::collections::HashMap;
+/// #[derive(Debug, PartialEq)]
+/// struct NotFound;
+///
+/// let mut names = HashMap::new();
+/// names.insert(42, "  Foo ".to_string());
+/// let name = fn_expr!{ Result<&str, NotFound>:
+///     names.get(&42).ok_or(NotFound)?.trim().into_ok_ref()
+/// };
+/// assert_eq!(Ok("Foo"), name);
+/// ```
+///
+/// [`IntoSomeRef`]: trait.IntoSomeRef.html
+pub trait IntoOkRef {
+    /// This method wraps a reference to `self` into an `Ok` and returns it.
+    #[allow(clippy::wrong_self_convention)]
+    fn into_ok_ref<E>(&self) -> Result<&Self, E>;
+}
+
+/// Implementation of trait `IntoOkRef` for
+/// all types.
+impl<T: ?Sized> IntoOkRef for T {
+    #[inline(always)]
+    fn into_ok_ref<E>(&self) -> Result<&Self, E> {
+        Ok(self)
+    }
+}
+
+/// This trait, which is implemented for all types, including unsized ones,
+/// provides the method `into_ok_mut`, which wraps a mutable reference to the
+/// value on which it is called into a `Result::Ok`. This is the mutable
+/// counterpart of [`IntoOkRef`]. The error type is left to inference.
+/// As described for [`IntoSomeMut`], the reference a mutable reference is
+/// derived from has to be moved into a wrapped expression.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// #[derive(Debug)]
+/// struct Empty;
+///
+/// let mut names = vec!["foo".to_string()];
+/// let names_ref = &mut names;
+/// let last = fn_expr!{ Result<&mut String, Empty>:
+///     { names_ref }.last_mut().ok_or(Empty)?.into_ok_mut()
+/// };
+/// last.unwrap().push_str("bar");
+/// assert_eq!("foobar", names[0]);
+/// ```
+///
+/// [`IntoOkRef`]: trait.IntoOkRef.html
+/// [`IntoSomeMut`]: trait.IntoSomeMut.html
+pub trait IntoOkMut {
+    /// This method wraps a mutable reference to `self` into an `Ok` and returns it.
+    #[allow(clippy::wrong_self_convention)]
+    fn into_ok_mut<E>(&mut self) -> Result<&mut Self, E>;
+}
+
+/// Implementation of trait `IntoOkMut` for
+/// all types.
+impl<T: ?Sized> IntoOkMut for T {
+    #[inline(always)]
+    fn into_ok_mut<E>(&mut self) -> Result<&mut Self, E> {
+        Ok(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_ok_or_into`, which moves the value on which it
+/// is called into a `Result::Ok` with the error type given as type parameter
+/// of the method. Other than with [`IntoOk`], where the error type is a
+/// parameter of the trait, the error type can be pinned at the end of a call
+/// chain via turbofish, e.g. `value.into_ok_or_into::<MyErr>()`, so a chain
+/// which is later combined with fallible steps already has the final error type.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// #[derive(Debug, PartialEq)]
+/// struct ConfigErr;
+///
+/// let port = "8080".len().into_ok_or_into::<ConfigErr>();
+/// assert_eq!(Ok(4), port);
+/// let checked = port.and_then(|p| if p > 0 { Ok(p) } else { Err(ConfigErr) });
+/// assert_eq!(Ok(4), checked);
+/// ```
+///
+/// [`IntoOk`]: trait.IntoOk.html
+pub trait IntoOkOrInto: Sized {
+    /// This method moves `self` into an `Ok` of a `Result` with error type `F` and returns it.
+    fn into_ok_or_into<F>(self) -> Result<Self, F>;
+}
+
+/// Implementation of trait `IntoOkOrInto` for
+/// all sized types.
+impl<T> IntoOkOrInto for T {
+    #[inline(always)]
+    fn into_ok_or_into<F>(self) -> Result<Self, F> {
+        Ok(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_err`, which moves the
+/// value on which it is called into a `Result::Err`.
+/// This is particularly useful when having to wrap a value into
+/// an `Err` at the end of a call chain, e.g. on paths constructing a failure.
+///
+/// # Example:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let res : Result<(),String> = "foo bar ".trim().to_uppercase().into_err();
+/// assert_eq!("FOO BAR", res.unwrap_err());
+/// ```
+///
+/// This can e.g. be used inside of
+/// an expression wrapped in a [`fn_expr!`] or [`fn_block!`] macro.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// fn build_message(code: u32) -> Result<String, String> {
+///     if code > 0 {
+///         Ok(format!("failed with code {}", code))
+///     } else {
+///         Err("no failure code".to_string())
+///     }
+/// }
+/// let code = 7;
+/// let res = fn_expr!{ Result<u32,String>: build_message(code)?.into_err() };
+/// assert_eq!(Err("failed with code 7".to_string()), res);
+/// ```
+///
+/// [`fn_expr!`]: macro.fn_expr.html
+/// [`fn_block!`]: macro.fn_block.html
+pub trait IntoErr<T>: Sized {
+    /// This method moves `self` into an `Err` and returns it.
+    fn into_err(self) -> Result<T, Self>;
+}
+
+/// Implementation of trait `IntoErr` for
+/// all sized types.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::IntoErr;
+/// let five : Result<(),u32> = 5.into_err();
+/// assert_eq!(Err(5), five);
+/// ```
+///
+impl<T, E> IntoErr<T> for E {
+    #[inline(always)]
+    fn into_err(self) -> Result<T, Self> {
+        Err(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_ok_err`, which moves the
+/// value on which it is called into a `Result::Err` of a `Result`
+/// whose success type is `Infallible`. This is useful when implementing
+/// a `Result` returning trait method, where the success case is unreachable.
+/// Since `Infallible` has no values, a `match` on the result only needs
+/// an arm for the `Err` case. The method is equivalent to calling
+/// [`into_err`] with the success type pinned to `Infallible`.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// use std::convert::Infallible;
+///
+/// let res: Result<Infallible, &str> = "always fails".into_ok_err();
+/// let msg = match res {
+///     Err(msg) => msg,
+/// };
+/// assert_eq!("always fails", msg);
+/// ```
+///
+/// [`into_err`]: trait.IntoErr.html#tymethod.into_err
+pub trait IntoOkErr: Sized {
+    /// This method moves `self` into an `Err` and returns it.
+    fn into_ok_err(self) -> Result<::core::convert::Infallible, Self>;
+}
+
+/// Implementation of trait `IntoOkErr` for
+/// all sized types.
+impl<T> IntoOkErr for T {
+    #[inline(always)]
+    fn into_ok_err(self) -> Result<::core::convert::Infallible, Self> {
+        Err(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_left`, which moves the
+/// value on which it is called into an `Either::Left` of the [`either`] crate.
+/// This is useful for sum types which are not about success and failure,
+/// analogous to [`IntoOk`] for `Result`.
+/// - *Note*: This trait is only available if the crate feature "either" is enabled.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate fn_block;
+/// # extern crate either;
+/// # use fn_block::*;
+/// use either::Either;
+///
+/// # fn main() {
+/// let o = Some("Foobar");
+/// let e: Option<Either<String, usize>> = fn_expr!{ o?.to_lowercase().into_left().into_some() };
+/// assert_eq!(Some(Either::Left("foobar".to_string())), e);
+/// # }
+/// ```
+///
+/// [`either`]: https://crates.io/crates/either
+/// [`IntoOk`]: trait.IntoOk.html
+#[cfg(feature = "either")]
+pub trait IntoLeft<R>: Sized {
+    /// This method moves `self` into an `Either::Left` and returns it.
+    fn into_left(self) -> either::Either<Self, R>;
+}
+
+/// Implementation of trait `IntoLeft` for
+/// all sized types.
+#[cfg(feature = "either")]
+impl<T, R> IntoLeft<R> for T {
+    #[inline(always)]
+    fn into_left(self) -> either::Either<Self, R> {
+        either::Either::Left(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_right`, which moves the
+/// value on which it is called into an `Either::Right` of the [`either`] crate.
+/// - *Note*: This trait is only available if the crate feature "either" is enabled.
+///
+/// # Example:
+///
+/// ```rust
+/// # extern crate fn_block;
+/// # extern crate either;
+/// # use fn_block::*;
+/// use either::Either;
+///
+/// # fn main() {
+/// let e: Either<String, usize> = "Foobar".len().into_right();
+/// assert_eq!(Either::Right(6), e);
+/// # }
+/// ```
+///
+/// [`either`]: https://crates.io/crates/either
+#[cfg(feature = "either")]
+pub trait IntoRight<L>: Sized {
+    /// This method moves `self` into an `Either::Right` and returns it.
+    fn into_right(self) -> either::Either<L, Self>;
+}
+
+/// Implementation of trait `IntoRight` for
+/// all sized types.
+#[cfg(feature = "either")]
+impl<T, L> IntoRight<L> for T {
+    #[inline(always)]
+    fn into_right(self) -> either::Either<L, Self> {
+        either::Either::Right(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_array_one`, which moves the
+/// value on which it is called into an array of length one.
+/// Other than collecting into a `Vec`, this does not allocate, which
+/// makes it useful for APIs expecting fixed size arrays or slices.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// let names = "foo".to_uppercase().into_array_one();
+/// assert_eq!(["FOO".to_string()], names);
+/// assert_eq!("FOO", names.join(", "));
+/// ```
+pub trait IntoArrayOne: Sized {
+    /// This method moves `self` into an array of length one and returns it.
+    fn into_array_one(self) -> [Self; 1];
+}
+
+/// Implementation of trait `IntoArrayOne` for
+/// all sized types.
+impl<T> IntoArrayOne for T {
+    #[inline(always)]
+    fn into_array_one(self) -> [Self; 1] {
+        [self]
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_box`, which moves the value on which it is
+/// called into a `Box`. This allows ending a call chain with a boxed value,
+/// without wrapping the whole chain into a call of `Box::new`. The returned
+/// box can be unsized at the assignment site, e.g. into a `Box<dyn Trait>`.
+/// - *Note*: This trait is only available if the crate feature "std" is enabled, which it is by default.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::fmt::Display;
+/// let o = Some("42");
+/// let boxed = fn_expr!{ o?.parse::<u32>().ok()?.into_box().into_some() };
+/// let display: Box<dyn Display> = boxed.unwrap();
+/// assert_eq!("42", display.to_string());
+/// ```
+#[cfg(feature = "std")]
+pub trait IntoBox: Sized {
+    /// This method moves `self` into a `Box` and returns it.
+    fn into_box(self) -> Box<Self>;
+}
+
+/// Implementation of trait `IntoBox` for
+/// all sized types.
+#[cfg(feature = "std")]
+impl<T> IntoBox for T {
+    #[inline(always)]
+    fn into_box(self) -> Box<Self> {
+        Box::new(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_rc`, which moves the value on which it is
+/// called into a reference counted `Rc`. This allows ending a call chain
+/// with a shared value, e.g. when building graph-like structures.
+/// - *Note*: This trait is only available if the crate feature "std" is enabled, which it is by default.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::rc::Rc;
+/// let o = Some("Node");
+/// let node = fn_expr!{ o?.to_lowercase().into_rc().into_some() }.unwrap();
+/// let parent_ref = Rc::clone(&node);
+/// assert_eq!("node", *parent_ref);
+/// assert_eq!(2, Rc::strong_count(&node));
+/// ```
+#[cfg(feature = "std")]
+pub trait IntoRc: Sized {
+    /// This method moves `self` into an `Rc` and returns it.
+    fn into_rc(self) -> Rc<Self>;
+}
+
+/// Implementation of trait `IntoRc` for
+/// all sized types.
+#[cfg(feature = "std")]
+impl<T> IntoRc for T {
+    #[inline(always)]
+    fn into_rc(self) -> Rc<Self> {
+        Rc::new(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_arc`, which moves the value on which it is
+/// called into an atomically reference counted `Arc`. This allows ending
+/// a call chain with a value which is shared with other threads.
+/// - *Note*: This trait is only available if the crate feature "std" is enabled, which it is by default.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::sync::Arc;
+/// use std::thread;
+/// let o = Some("Config");
+/// let config = fn_expr!{ o?.to_lowercase().into_arc().into_some() }.unwrap();
+/// let shared = Arc::clone(&config);
+/// let len = thread::spawn(move || shared.len()).join().unwrap();
+/// assert_eq!(6, len);
+/// assert_eq!("config", *config);
+/// ```
+#[cfg(feature = "std")]
+pub trait IntoArc: Sized {
+    /// This method moves `self` into an `Arc` and returns it.
+    fn into_arc(self) -> Arc<Self>;
+}
+
+/// Implementation of trait `IntoArc` for
+/// all sized types.
+#[cfg(feature = "std")]
+impl<T> IntoArc for T {
+    #[inline(always)]
+    fn into_arc(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_mutex`, which moves the value on which it is
+/// called into a `Mutex`. Combined with [`IntoArc`], the initialization of
+/// state shared between threads reads as a single call chain.
+/// - *Note*: This trait is only available if the crate feature "std" is enabled, which it is by default.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+/// use std::sync::{Arc, Mutex};
+/// let limit = "10";
+/// let state: Result<Arc<Mutex<u32>>, ParseIntError> = fn_expr!{
+///     limit.parse::<u32>()?.into_mutex().into_arc().into_ok()
+/// };
+/// let state = state.unwrap();
+/// *state.lock().unwrap() += 1;
+/// assert_eq!(11, *state.lock().unwrap());
+/// ```
+///
+/// [`IntoArc`]: trait.IntoArc.html
+#[cfg(feature = "std")]
+pub trait IntoMutex: Sized {
+    /// This method moves `self` into a `Mutex` and returns it.
+    fn into_mutex(self) -> Mutex<Self>;
+}
+
+/// Implementation of trait `IntoMutex` for
+/// all sized types.
+#[cfg(feature = "std")]
+impl<T> IntoMutex for T {
+    #[inline(always)]
+    fn into_mutex(self) -> Mutex<Self> {
+        Mutex::new(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_rw_lock`, which moves the value on which it is
+/// called into a `RwLock`. Combined with [`IntoArc`], read-heavy state shared
+/// between threads can be built at the end of a fallible call chain.
+/// - *Note*: This trait is only available if the crate feature "std" is enabled, which it is by default.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+/// use std::sync::{Arc, RwLock};
+/// let limits = "10,20";
+/// let state: Result<Arc<RwLock<Vec<u32>>>, ParseIntError> = fn_expr!{
+///     limits.split(',').map(str::parse).collect::<Result<Vec<u32>, _>>()?
+///         .into_rw_lock().into_arc().into_ok()
+/// };
+/// let state = state.unwrap();
+/// assert_eq!(20, state.read().unwrap()[1]);
+/// ```
+///
+/// [`IntoArc`]: trait.IntoArc.html
+#[cfg(feature = "std")]
+pub trait IntoRwLock: Sized {
+    /// This method moves `self` into a `RwLock` and returns it.
+    fn into_rw_lock(self) -> RwLock<Self>;
+}
+
+/// Implementation of trait `IntoRwLock` for
+/// all sized types.
+#[cfg(feature = "std")]
+impl<T> IntoRwLock for T {
+    #[inline(always)]
+    fn into_rw_lock(self) -> RwLock<Self> {
+        RwLock::new(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_ref_cell`, which moves the value on which it is
+/// called into a `RefCell`. This allows ending a call chain with a value
+/// providing single-threaded interior mutability. Combined with [`IntoRc`],
+/// a shared mutable `Rc<RefCell<T>>` can be created at the end of a chain.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// let count = "41".parse::<u32>().unwrap().into_ref_cell();
+/// *count.borrow_mut() += 1;
+/// assert_eq!(42, *count.borrow());
+/// ```
+///
+/// # Example combined with `IntoRc`:
+///
+/// ```rust
+/// # use fn_block::*;
+/// # #[cfg(feature = "std")] {
+/// use std::rc::Rc;
+/// let names = vec!["foo"].into_ref_cell().into_rc();
+/// let other = Rc::clone(&names);
+/// other.borrow_mut().push("bar");
+/// assert_eq!(vec!["foo", "bar"], *names.borrow());
+/// # }
+/// ```
+///
+/// - *Note*: The second example requires `IntoRc`, which is only available if the crate feature "std" is enabled.
+///
+/// [`IntoRc`]: trait.IntoRc.html
+pub trait IntoRefCell: Sized {
+    /// This method moves `self` into a `RefCell` and returns it.
+    fn into_ref_cell(self) -> RefCell<Self>;
+}
+
+/// Implementation of trait `IntoRefCell` for
+/// all sized types.
+impl<T> IntoRefCell for T {
+    #[inline(always)]
+    fn into_ref_cell(self) -> RefCell<Self> {
+        RefCell::new(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `into_cell`, which moves the value on which it is
+/// called into a `Cell`. Note that the value of a `Cell` can only be read
+/// via `Cell::get` if the type implements `Copy`; other values can still
+/// be swapped out via `Cell::replace` or `Cell::take`.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let o = Some("42");
+/// let counter = fn_expr!{ o?.parse::<u32>().ok()?.into_cell().into_some() }.unwrap();
+/// counter.set(counter.get() + 1);
+/// assert_eq!(43, counter.get());
+/// ```
+pub trait IntoCell: Sized {
+    /// This method moves `self` into a `Cell` and returns it.
+    fn into_cell(self) -> Cell<Self>;
+}
+
+/// Implementation of trait `IntoCell` for
+/// all sized types.
+impl<T> IntoCell for T {
+    #[inline(always)]
+    fn into_cell(self) -> Cell<Self> {
+        Cell::new(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `wrap_with`, which passes the value on which it is
+/// called to the given wrapper constructor and returns the wrapped value.
+/// This generalizes [`into_some`] and [`into_ok`] to arbitrary wrapper types,
+/// such as newtypes or enum variants holding a single value. The method does
+/// the same as [`pipe`], but expresses the intent of ending a call chain
+/// by wrapping its value.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// #[derive(Debug, PartialEq)]
+/// struct Name(String);
+///
+/// let o = Some(" Foo ");
+/// let name = fn_expr!{ o?.trim().to_string().wrap_with(Name).wrap_with(Some) };
+/// assert_eq!(Some(Name("Foo".to_string())), name);
+/// ```
+///
+/// [`into_some`]: trait.IntoSome.html#tymethod.into_some
+/// [`into_ok`]: trait.IntoOk.html#tymethod.into_ok
+/// [`pipe`]: trait.Pipe.html#tymethod.pipe
+pub trait WrapWith: Sized {
+    /// This method passes `self` to the wrapper constructor `f` and returns the result.
+    fn wrap_with<U, F>(self, f: F) -> U
+    where
+        F: FnOnce(Self) -> U;
+}
+
+/// Implementation of trait `WrapWith` for
+/// all sized types.
+impl<T> WrapWith for T {
+    #[inline(always)]
+    fn wrap_with<U, F>(self, f: F) -> U
+    where
+        F: FnOnce(Self) -> U,
+    {
+        f(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `some_if`, which wraps the value on which it is
+/// called into a `Some` if the given predicate holds true for it, and
+/// returns `None` otherwise. The predicate only borrows the value, so it
+/// is not consumed by the check. The complementary method `none_if`
+/// returns `None` if the predicate holds true, which avoids negating the
+/// condition inside of the closure. These are the method counterparts of
+/// the free functions [`fns::some_if`] and [`fns::none_if`], which read
+/// more naturally at the end of a call chain.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate fn_block;
+/// # use fn_block::*;
+/// # fn main() {
+/// let input = Some("42");
+/// let even = fn_expr!{ input?.parse::<i32>().ok()?.some_if(|&i| i > 0)?.some_if(|&i| i % 2 == 0) };
+/// assert_eq!(Some(42), even);
+///
+/// let input = Some("-42");
+/// let even = fn_expr!{ input?.parse::<i32>().ok()?.some_if(|&i| i > 0)?.some_if(|&i| i % 2 == 0) };
+/// assert_eq!(None, even);
+/// # }
+/// ```
+///
+/// # Example using `none_if`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate fn_block;
+/// # use fn_block::*;
+/// # fn main() {
+/// let line = Some("  ");
+/// let trimmed = fn_expr!{ line?.trim().none_if(|s| s.is_empty())?.to_uppercase().into_some() };
+/// assert_eq!(None, trimmed);
+///
+/// let line = Some(" foo ");
+/// let trimmed = fn_expr!{ line?.trim().none_if(|s| s.is_empty())?.to_uppercase().into_some() };
+/// assert_eq!(Some("FOO".to_string()), trimmed);
+/// # }
+/// ```
+///
+/// [`fns::some_if`]: fns/fn.some_if.html
+/// [`fns::none_if`]: fns/fn.none_if.html
+pub trait SomeIf: Sized {
+    /// This method moves `self` into a `Some` if `predicate` returns
+    /// `true` for a reference to `self`, otherwise `None` is returned.
+    fn some_if<F>(self, predicate: F) -> Option<Self>
+    where
+        F: FnOnce(&Self) -> bool;
+
+    /// This method returns `None` if `predicate` returns `true` for a
+    /// reference to `self`, otherwise `self` is moved into a `Some`.
+    fn none_if<F>(self, predicate: F) -> Option<Self>
+    where
+        F: FnOnce(&Self) -> bool;
+}
+
+/// Implementation of trait `SomeIf` for
+/// all sized types.
+impl<T> SomeIf for T {
+    fn some_if<F>(self, predicate: F) -> Option<Self>
+    where
+        F: FnOnce(&Self) -> bool,
+    {
+        fns::some_if(self, predicate)
+    }
+
+    fn none_if<F>(self, predicate: F) -> Option<Self>
+    where
+        F: FnOnce(&Self) -> bool,
+    {
+        fns::none_if(self, predicate)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `ok_or_if`, which wraps the value on which it is
+/// called into an `Ok` if the given predicate holds true for it, and
+/// returns the given error otherwise. The lazy variant `ok_or_else_if`
+/// only creates the error if the predicate does not hold. This is the
+/// `Result` counterpart of [`SomeIf`] and pairs naturally with a `?`
+/// directly after the call. The methods are the method counterparts of the
+/// free functions [`fns::ok_if`] and [`fns::ok_if_else`].
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// #[derive(Debug, PartialEq)]
+/// enum NameErr { Empty, TooLong(usize) }
+///
+/// let name = " Foo ";
+/// let res = fn_expr!{ Result<String, NameErr>:
+///     name.trim()
+///         .ok_or_if(|s| !s.is_empty(), NameErr::Empty)?
+///         .ok_or_else_if(|s| s.len() < 3, || NameErr::TooLong(name.len()))?
+///         .to_uppercase()
+///         .into_ok()
+/// };
+/// assert_eq!(Err(NameErr::TooLong(5)), res);
+/// ```
+///
+/// [`SomeIf`]: trait.SomeIf.html
+/// [`fns::ok_if`]: fns/fn.ok_if.html
+/// [`fns::ok_if_else`]: fns/fn.ok_if_else.html
+pub trait OkIf: Sized {
+    /// This method moves `self` into an `Ok` if `predicate` returns
+    /// `true` for a reference to `self`, otherwise `Err(err)` is returned.
+    fn ok_or_if<E, F>(self, predicate: F, err: E) -> Result<Self, E>
+    where
+        F: FnOnce(&Self) -> bool;
+
+    /// This method moves `self` into an `Ok` if `predicate` returns
+    /// `true` for a reference to `self`, otherwise an `Err` holding the
+    /// result of `err_fn` is returned. `err_fn` is only called if needed.
+    fn ok_or_else_if<E, F, G>(self, predicate: F, err_fn: G) -> Result<Self, E>
+    where
+        F: FnOnce(&Self) -> bool,
+        G: FnOnce() -> E;
+}
+
+/// Implementation of trait `OkIf` for
+/// all sized types.
+impl<T> OkIf for T {
+    fn ok_or_if<E, F>(self, predicate: F, err: E) -> Result<Self, E>
+    where
+        F: FnOnce(&Self) -> bool,
+    {
+        fns::ok_if(self, predicate, err)
+    }
+
+    fn ok_or_else_if<E, F, G>(self, predicate: F, err_fn: G) -> Result<Self, E>
+    where
+        F: FnOnce(&Self) -> bool,
+        G: FnOnce() -> E,
+    {
+        fns::ok_if_else(self, predicate, err_fn)
+    }
+}
+
+/// This trait, which is implemented for all references to types
+/// implementing `ToOwned`, provides the method `into_cow_borrowed`, which moves the
+/// reference on which it is called into a `Cow::Borrowed`.
+/// Together with [`IntoCowOwned`] this allows ending a call chain with a `Cow`,
+/// so a chain that may either produce a borrowed or an owned value does not
+/// need to allocate in the borrowed case.
+/// - *Note*: This trait is only available if the crate feature "std" is enabled, which it is by default.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::borrow::Cow;
+/// fn prefix(o: Option<&str>) -> Option<Cow<str>> {
+///     fn_expr!{{
+///         let s = o?.get(0..3)?;
+///         if s.chars().all(char::is_lowercase) {
+///             s.into_cow_borrowed().into_some()
+///         } else {
+///             s.to_lowercase().into_cow_owned().into_some()
+///         }
+///     }}
+/// }
+/// assert_eq!(Some(Cow::Borrowed("foo")), prefix(Some("foobar")));
+/// assert_eq!(Some(Cow::Owned::<str>("foo".to_string())), prefix(Some("Foobar")));
+/// ```
+///
+/// [`IntoCowOwned`]: trait.IntoCowOwned.html
+#[cfg(feature = "std")]
+pub trait IntoCowBorrowed<'a, B: ?Sized + ToOwned> {
+    /// This method moves `self` into a `Cow::Borrowed` and returns it.
+    fn into_cow_borrowed(self) -> Cow<'a, B>;
+}
+
+/// Implementation of trait `IntoCowBorrowed` for
+/// all references to types implementing `ToOwned`.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::IntoCowBorrowed;
+/// # use std::borrow::Cow;
+/// let cow = "foo".into_cow_borrowed();
+/// assert_eq!(Cow::Borrowed("foo"), cow);
+/// ```
+///
+#[cfg(feature = "std")]
+impl<'a, B: ?Sized + ToOwned> IntoCowBorrowed<'a, B> for &'a B {
+    #[inline(always)]
+    fn into_cow_borrowed(self) -> Cow<'a, B> {
+        Cow::Borrowed(self)
+    }
+}
+
+/// This trait, which is implemented for the owned forms of the unsized standard
+/// library types (`String`, `Vec<T>`, `PathBuf`, `OsString` and `CString`), provides
+/// the method `into_cow_owned`, which moves the value on which it is called into a
+/// `Cow::Owned` of the borrowed form (e.g. a `String` into a `Cow<str>`).
+/// See [`IntoCowBorrowed`] for an example ending a call chain with a `Cow`.
+///
+/// The borrowed form is defined by an associated type, so the type of the
+/// resulting `Cow` can always be inferred from the owned value.
+/// - *Note*: This trait is only available if the crate feature "std" is enabled, which it is by default.
+///
+/// [`IntoCowBorrowed`]: trait.IntoCowBorrowed.html
+#[cfg(feature = "std")]
+pub trait IntoCowOwned: Sized {
+    /// The borrowed form of the implementing type.
+    type Borrowed: ?Sized + ToOwned<Owned = Self>;
+
+    /// This method moves `self` into a `Cow::Owned` and returns it.
+    fn into_cow_owned<'a>(self) -> Cow<'a, Self::Borrowed> {
+        Cow::Owned(self)
+    }
+}
+
+/// Implementation of trait `IntoCowOwned` for `String`.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::IntoCowOwned;
+/// # use std::borrow::Cow;
+/// let cow = "foo".to_string().into_cow_owned();
+/// assert_eq!(Cow::Owned::<str>("foo".to_string()), cow);
+/// ```
+///
+#[cfg(feature = "std")]
+impl IntoCowOwned for String {
+    type Borrowed = str;
+}
+
+/// Implementation of trait `IntoCowOwned` for `Vec`.
+///
+/// # Example
+///
+/// ```rust
+/// # use fn_block::IntoCowOwned;
+/// # use std::borrow::Cow;
+/// let cow = vec![1, 2].into_cow_owned();
+/// assert_eq!(Cow::Owned::<[u32]>(vec![1, 2]), cow);
+/// ```
+///
+#[cfg(feature = "std")]
+impl<T: Clone> IntoCowOwned for Vec<T> {
+    type Borrowed = [T];
+}
+
+/// Implementation of trait `IntoCowOwned` for `PathBuf`.
+#[cfg(feature = "std")]
+impl IntoCowOwned for ::std::path::PathBuf {
+    type Borrowed = ::std::path::Path;
+}
+
+/// Implementation of trait `IntoCowOwned` for `OsString`.
+#[cfg(feature = "std")]
+impl IntoCowOwned for ::std::ffi::OsString {
+    type Borrowed = ::std::ffi::OsStr;
+}
+
+/// Implementation of trait `IntoCowOwned` for `CString`.
+#[cfg(feature = "std")]
+impl IntoCowOwned for ::std::ffi::CString {
+    type Borrowed = ::std::ffi::CStr;
+}
+
+/// This trait, which is implemented for all types implementing `ToOwned`, including
+/// all `Clone` types and unsized types like `str` and `[T]`, provides the method
+/// `into_cow`, which wraps a reference to the value on which it is called into a
+/// `Cow::Borrowed`. Other than [`IntoCowBorrowed`], which is called on a reference,
+/// the method borrows `self`, so it can be called directly on an owned value or at
+/// the end of a call chain producing a reference. To end a call chain with an owned
+/// value, use [`IntoCowOwned`].
+/// - *Note*: This trait is only available if the crate feature "std" is enabled, which it is by default.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::borrow::Cow;
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Config { port: u16 }
+///
+/// let configs = vec![Config { port: 80 }, Config { port: 8080 }];
+/// let found = fn_expr!{ configs.iter().find(|c| c.port > 1024)?.into_cow().into_some() };
+/// let mut config: Cow<Config> = found.unwrap();
+/// config.to_mut().port += 1;
+/// assert_eq!(8081, config.port);
+/// assert_eq!(8080, configs[1].port);
+/// let tail: Cow<[Config]> = configs[1..].into_cow();
+/// assert_eq!(1, tail.len());
+/// ```
+///
+/// [`IntoCowBorrowed`]: trait.IntoCowBorrowed.html
+/// [`IntoCowOwned`]: trait.IntoCowOwned.html
+#[cfg(feature = "std")]
+pub trait IntoCow: ToOwned {
+    /// This method wraps a reference to `self` into a `Cow::Borrowed` and returns it.
+    #[allow(clippy::wrong_self_convention)]
+    fn into_cow(&self) -> Cow<'_, Self>;
+}
+
+/// Implementation of trait `IntoCow` for
+/// all types implementing `ToOwned`.
+#[cfg(feature = "std")]
+impl<T: ?Sized + ToOwned> IntoCow for T {
+    #[inline(always)]
+    fn into_cow(&self) -> Cow<'_, Self> {
+        Cow::Borrowed(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `pipe`, which passes the value on which
+/// it is called to the given function and returns the function's result.
+/// This allows applying free functions in method position, so a call chain
+/// does not have to be broken up into nested function calls.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// fn double(i: u32) -> u32 { i * 2 }
+/// let s = 21.pipe(double).to_string();
+/// assert_eq!("42", s);
+/// ```
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::str::from_utf8;
+/// use std::error::Error;
+/// struct ConvertErr();
+/// impl <T: Error> From<T> for ConvertErr {
+///     fn from(_: T) -> ConvertErr {ConvertErr()}
+/// }
+/// let bytes : &[u8] = &[0x0020,0x0034,0x0032];
+/// let res_int = fn_expr!{ Result<u32,ConvertErr>:
+///     bytes.pipe(from_utf8)?.trim().pipe(str::parse::<u32>)?.into_ok()
+/// }.unwrap_or(0u32);
+/// assert_eq!(res_int, 42);
+/// ```
+///
+/// Generic functions and closures can be piped as well, as long as the
+/// types can be inferred:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let bytes = [42u8, 0, 0, 0];
+/// let doubled = fn_expr!{ bytes.pipe(u32::from_le_bytes).pipe(|i| i * 2).into_some() };
+/// assert_eq!(Some(84), doubled);
+/// ```
+pub trait Pipe: Sized {
+    /// This method passes `self` to `f` and returns the result of `f`.
+    fn pipe<U, F>(self, f: F) -> U
+    where
+        F: FnOnce(Self) -> U;
+
+    /// This method passes a reference to `self` to `f` and returns the result of `f`.
+    /// Other than [`pipe`], the value is not consumed, so it can still be used after
+    /// the function was applied.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use fn_block::*;
+    /// let name = "Foo".to_string();
+    /// let len = name.pipe_ref(String::len);
+    /// assert_eq!((3, "Foo".to_string()), (len, name));
+    /// ```
+    ///
+    /// [`pipe`]: trait.Pipe.html#tymethod.pipe
+    fn pipe_ref<U, F>(&self, f: F) -> U
+    where
+        F: FnOnce(&Self) -> U;
+
+    /// This method passes a mutable reference to `self` to `f` and returns the result of `f`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use fn_block::*;
+    /// let mut names = vec!["foo", "bar"];
+    /// let last = names.pipe_mut(Vec::pop);
+    /// assert_eq!(Some("bar"), last);
+    /// assert_eq!(vec!["foo"], names);
+    /// ```
+    fn pipe_mut<U, F>(&mut self, f: F) -> U
+    where
+        F: FnOnce(&mut Self) -> U;
+}
+
+/// Implementation of trait `Pipe` for
+/// all sized types.
+impl<T> Pipe for T {
+    fn pipe<U, F>(self, f: F) -> U
+    where
+        F: FnOnce(Self) -> U,
+    {
+        f(self)
+    }
+
+    #[inline(always)]
+    fn pipe_ref<U, F>(&self, f: F) -> U
+    where
+        F: FnOnce(&Self) -> U,
+    {
+        f(self)
+    }
+
+    #[inline(always)]
+    fn pipe_mut<U, F>(&mut self, f: F) -> U
+    where
+        F: FnOnce(&mut Self) -> U,
+    {
+        f(self)
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `tap`, which passes a reference to the value on which
+/// it is called to the given function and then returns the value unchanged.
+/// This allows performing side effects, such as debug output, in the middle
+/// of a call chain without breaking it up.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let o = Some("Foobar");
+/// let res = fn_expr!{ o?.get(0..3)?.tap(|s| eprintln!("slice: {}", s)).to_lowercase().into_some() };
+/// assert_eq!("foo", res.unwrap());
+/// ```
+pub trait Tap: Sized {
+    /// This method passes a reference to `self` to `f` and returns `self`.
+    fn tap<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&Self);
+}
+
+/// Implementation of trait `Tap` for
+/// all sized types.
+impl<T> Tap for T {
+    fn tap<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&Self),
+    {
+        f(&self);
+        self
+    }
+}
+
+/// This trait, which is implemented for all sized types,
+/// provides the method `tap_mut`, which passes a mutable reference to the value
+/// on which it is called to the given function and then returns the value.
+/// Other than [`Tap`], this allows small in-place adjustments in the middle of
+/// a call chain, without having to introduce a mutable binding. The value is
+/// moved into the method, so the original binding cannot be used afterwards.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let input = Some("c,a,b");
+/// let sorted = fn_expr!{
+///     input?.split(',').collect::<Vec<_>>().tap_mut(|v| v.sort()).join(",").into_some()
+/// };
+/// assert_eq!("a,b,c", sorted.unwrap());
+/// ```
+///
+/// The original binding is consumed:
+///
+/// ```compile_fail
+/// # use fn_block::*;
+/// let v = vec![3, 1, 2];
+/// let sorted = v.tap_mut(|v| v.sort());
+/// assert_eq!(3, v.len());
+/// ```
+///
+/// [`Tap`]: trait.Tap.html
+pub trait TapMut: Sized {
+    /// This method passes a mutable reference to `self` to `f` and returns `self`.
+    fn tap_mut<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self);
+}
+
+/// Implementation of trait `TapMut` for
+/// all sized types.
+impl<T> TapMut for T {
+    fn tap_mut<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+}
+
+/// This trait, which is implemented for `Option`, provides the method
+/// `flat_map_some`, which calls the given function with the value wrapped in a
+/// `Some` and returns the function's result. If called on a `None`, the function
+/// is not called and `None` is returned. The method is equivalent to `Option::and_then`
+/// and is provided under a name that may be more familiar to users coming from
+/// other languages.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let o = Some("Foobar");
+/// let s = fn_expr!{ o.flat_map_some(|s| s.get(0..3))?.to_lowercase().into_some() };
+/// assert_eq!("foo", s.unwrap());
+/// ```
+pub trait FlatMapSome<T> {
+    /// This method calls `f` with the value wrapped in `self`, if `self` is a `Some`.
+    fn flat_map_some<U, F>(self, f: F) -> Option<U>
+    where
+        F: FnOnce(T) -> Option<U>;
+}
+
+/// Implementation of trait `FlatMapSome` for
+/// all `Option` types.
+impl<T> FlatMapSome<T> for Option<T> {
+    fn flat_map_some<U, F>(self, f: F) -> Option<U>
+    where
+        F: FnOnce(T) -> Option<U>,
+    {
+        self.and_then(f)
+    }
+}
+
+/// This trait, which is implemented for `Option`, provides the method
+/// `map_some`, which transforms the value wrapped in a `Some` with the given
+/// function, and the method `map_some_or`, which additionally unwraps the result,
+/// returning a default value for `None`. The methods are equivalent to `Option::map`
+/// and `Option::map_or` and are provided for naming consistency with [`FlatMapSome`].
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// let o = Some("Foobar");
+/// assert_eq!(Some(6), o.map_some(str::len));
+/// assert_eq!(0, None::<&str>.map_some_or(0, str::len));
+/// ```
+///
+/// [`FlatMapSome`]: trait.FlatMapSome.html
+pub trait MapSome<T> {
+    /// This method transforms the value wrapped in `self` with `f`, if `self` is a `Some`.
+    fn map_some<U, F>(self, f: F) -> Option<U>
+    where
+        F: FnOnce(T) -> U;
+
+    /// This method transforms the value wrapped in `self` with `f`, if `self` is a `Some`,
+    /// and returns `default` otherwise.
+    fn map_some_or<U, F>(self, default: U, f: F) -> U
+    where
+        F: FnOnce(T) -> U;
+}
+
+/// Implementation of trait `MapSome` for
+/// all `Option` types.
+impl<T> MapSome<T> for Option<T> {
+    fn map_some<U, F>(self, f: F) -> Option<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        self.map(f)
+    }
+
+    fn map_some_or<U, F>(self, default: U, f: F) -> U
+    where
+        F: FnOnce(T) -> U,
+    {
+        self.map_or(default, f)
+    }
+}
+
+/// This trait, which is implemented for `Result`, provides the method
+/// `flat_map_ok`, which calls the given function with the value wrapped in an
+/// `Ok` and returns the function's result. If called on an `Err`, the function
+/// is not called and the error is returned unchanged. The method is equivalent
+/// to `Result::and_then` and is the `Result` counterpart of [`FlatMapSome`].
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+/// let parse = |s: &str| s.trim().parse::<u32>();
+/// let res_int = fn_expr!{ Result<u32,ParseIntError>:
+///     " 42".into_ok().flat_map_ok(parse)?.into_ok()
+/// };
+/// assert_eq!(Ok(42), res_int);
+/// ```
+///
+/// [`FlatMapSome`]: trait.FlatMapSome.html
+pub trait FlatMapOk<T, E> {
+    /// This method calls `f` with the value wrapped in `self`, if `self` is an `Ok`.
+    fn flat_map_ok<U, F>(self, f: F) -> Result<U, E>
+    where
+        F: FnOnce(T) -> Result<U, E>;
+}
+
+/// Implementation of trait `FlatMapOk` for
+/// all `Result` types.
+impl<T, E> FlatMapOk<T, E> for Result<T, E> {
+    fn flat_map_ok<U, F>(self, f: F) -> Result<U, E>
+    where
+        F: FnOnce(T) -> Result<U, E>,
+    {
+        self.and_then(f)
+    }
+}
+
+/// This trait, which is implemented for `Option`, provides additional
+/// methods that are useful inside of call chains, e.g. in an expression
+/// wrapped in a [`fn_expr!`] macro.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// let host = Some("localhost");
+/// let port = Some(8080);
+/// let url = fn_expr!{{
+///     let (scheme, host, port) = Some("http").zip3(host, port)?;
+///     format!("{}://{}:{}", scheme, host, port).into_some()
+/// }};
+/// assert_eq!("http://localhost:8080", url.unwrap());
+/// ```
+///
+/// [`fn_expr!`]: macro.fn_expr.html
+pub trait OptionExt<T>: Sized {
+    /// This method combines `self` and the two given options into a `Some` holding
+    /// a tuple of all three values, if all of them are a `Some`. If any of them
+    /// is `None`, `None` is returned. Note that all arguments are evaluated before
+    /// the method is called; the [`zip_all!`] macro only evaluates its arguments
+    /// up to the first `None`.
+    ///
+    /// [`zip_all!`]: macro.zip_all.html
+    fn zip3<B, C>(self, b: Option<B>, c: Option<C>) -> Option<(T, B, C)>;
+
+    /// This method combines `self` and the three given options into a `Some` holding
+    /// a tuple of all four values, if all of them are a `Some`. If any of them
+    /// is `None`, `None` is returned.
+    fn zip4<B, C, D>(self, b: Option<B>, c: Option<C>, d: Option<D>) -> Option<(T, B, C, D)>;
+
+    /// This method returns `true` if `self` is a `Some` holding a value equal to `x`.
+    /// It is useful as a guard in the middle of a chain, e.g. as the predicate
+    /// of [`some_if`], without having to compare against a `Some` constructed for
+    /// the comparison.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use fn_block::*;
+    /// use fn_block::fns::some_if;
+    /// let lang = Some("rust");
+    /// assert_eq!(Some(2018), some_if(2018, |_| lang.contains(&"rust")));
+    /// assert!(!None.contains(&"rust"));
+    /// ```
+    ///
+    /// [`some_if`]: fns/fn.some_if.html
+    fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq;
+
+    /// This method is the inverse of `Option::filter`: it returns `self` if it is a
+    /// `Some` holding a value for which the predicate `pred` does *not* hold true,
+    /// otherwise `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use fn_block::*;
+    /// assert_eq!(Some("foo"), Some("foo").filter_not(|s| s.is_empty()));
+    /// assert_eq!(None, Some("").filter_not(|s| s.is_empty()));
+    /// ```
+    fn filter_not<P>(self, pred: P) -> Option<T>
+    where
+        P: FnOnce(&T) -> bool;
+
+    /// This method returns the value of `self` as `Ok` if it is a `Some`. Otherwise
+    /// the fallible fallback `f` is called and its result is returned. The fallback
+    /// is only called if `self` is `None`. This is useful inside of a [`fn_try!`]
+    /// expression, where a failing fallback is propagated with `?` and handled in
+    /// the catch block.
+    ///
+    /// # Example using `fn_try!`:
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # use fn_block::*;
+    /// # #[cfg(feature = "unproven")] {
+    /// let cached: Option<u16> = None;
+    /// let configured = "http";
+    /// let port = fn_try!{
+    ///     cached.or_try(|| configured.parse::<u16>())?
+    ///     => catch {
+    ///         std::num::ParseIntError { .. } => 8080
+    ///     }
+    /// };
+    /// assert_eq!(8080, port);
+    /// # }
+    /// ```
+    ///
+    /// [`fn_try!`]: macro.fn_try.html
+    fn or_try<E, F>(self, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>;
+
+    /// This method calls `f` if `self` is `None` and returns `self` unchanged.
+    /// It complements `Option::inspect`, which is only called for a `Some`,
+    /// e.g. for logging or counting missing values in the middle of a chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # use fn_block::*;
+    /// let mut misses = 0;
+    /// let o: Option<&str> = None;
+    /// let len = fn_expr!{ o.tap_none(|| misses += 1)?.len().into_some() };
+    /// assert_eq!(None, len);
+    /// assert_eq!(1, misses);
+    /// ```
+    fn tap_none<F>(self, f: F) -> Option<T>
+    where
+        F: FnOnce();
+
+    /// This method returns `Ok(())` if `self` is `None`, and `Err(err)` if it is
+    /// a `Some`, dropping the contained value. This is useful for validations
+    /// enforcing the absence of a value, e.g. inside of a [`fn_try!`] block.
+    ///
+    /// # Example using `fn_try!`:
+    ///
+    /// ```rust
+    /// # #[macro_use]
+    /// # use fn_block::*;
+    /// # #[cfg(feature = "unproven")] {
+    /// struct Duplicate;
+    /// let ids = vec![1, 2, 2];
+    /// let unique = fn_try!{
+    ///     { ids.windows(2).find(|w| w[0] == w[1]).err_if_some(Duplicate)?; true }
+    ///     => catch {
+    ///         Duplicate => false
+    ///     }
+    /// };
+    /// assert!(!unique);
+    /// # }
+    /// ```
+    ///
+    /// [`fn_try!`]: macro.fn_try.html
+    fn err_if_some<E>(self, err: E) -> Result<(), E>;
+}
+
+/// Implementation of trait `OptionExt` for
+/// all `Option` types.
+impl<T> OptionExt<T> for Option<T> {
+    fn zip3<B, C>(self, b: Option<B>, c: Option<C>) -> Option<(T, B, C)> {
+        Some((self?, b?, c?))
+    }
+
+    fn zip4<B, C, D>(self, b: Option<B>, c: Option<C>, d: Option<D>) -> Option<(T, B, C, D)> {
+        Some((self?, b?, c?, d?))
+    }
+
+    fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        match *self {
+            Some(ref v) => v == x,
+            None => false,
+        }
+    }
+
+    fn filter_not<P>(self, pred: P) -> Option<T>
+    where
+        P: FnOnce(&T) -> bool,
+    {
+        match self {
+            Some(v) if !pred(&v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn or_try<E, F>(self, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        match self {
+            Some(v) => Ok(v),
+            None => f(),
+        }
+    }
+
+    #[inline(always)]
+    fn tap_none<F>(self, f: F) -> Option<T>
+    where
+        F: FnOnce(),
+    {
+        if self.is_none() {
+            f();
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn err_if_some<E>(self, err: E) -> Result<(), E> {
+        match self {
+            Some(_) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+/// This trait, which is implemented for `Result`, provides additional
+/// methods that are useful inside of call chains, e.g. in an expression
+/// wrapped in a [`fn_expr!`] or [`fn_try!`] macro.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+/// struct ConvertErr();
+/// impl From<ParseIntError> for ConvertErr {
+///     fn from(_: ParseIntError) -> ConvertErr {ConvertErr()}
+/// }
+/// let res_int = fn_expr!{ Result<u32,ConvertErr>:
+///     "42".parse::<u32>().map_err_into()
+/// }.unwrap_or(0u32);
+/// assert_eq!(res_int, 42);
+/// ```
+///
+/// [`fn_expr!`]: macro.fn_expr.html
+/// [`fn_try!`]: macro.fn_try.html
+pub trait ResultExt<T, E>: Sized {
+    /// This method converts the error of `self` into the error type `F` via `From`.
+    /// It is equivalent to `map_err(Into::into)` and is intended to be used right
+    /// before a terminal `?`, or at the end of a chain returning a `Result`, when the
+    /// target error type cannot be inferred by the `?` operator.
+    fn map_err_into<F>(self) -> Result<T, F>
+    where
+        F: From<E>;
+
+    /// This method returns `true` if `self` is an `Ok` holding a value equal to `x`.
+    /// Like [`OptionExt::contains`] it is intended as a guard inside of a chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use fn_block::*;
+    /// let r: Result<u32, String> = "42".parse::<u32>().map_err(|e| e.to_string());
+    /// assert!(r.contains_ok(&42));
+    /// assert!(!r.contains_err(&"invalid digit found in string".to_string()));
+    /// ```
+    ///
+    /// [`OptionExt::contains`]: trait.OptionExt.html#tymethod.contains
+    fn contains_ok(&self, x: &T) -> bool
+    where
+        T: PartialEq;
+
+    /// This method returns `true` if `self` is an `Err` holding an error equal to `e`.
+    fn contains_err(&self, e: &E) -> bool
+    where
+        E: PartialEq;
+
+    /// This method is the `Result` counterpart of `Option::filter`: it returns `self` if
+    /// it is an `Err`, or an `Ok` holding a value for which the predicate `pred` holds true.
+    /// Otherwise the value is passed to `err_fn` and the resulting error is returned as `Err`.
+    /// This is useful for validations inside of a [`fn_try!`] expression.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use fn_block::*;
+    /// let port = "80".parse::<u16>().map_err(|e| e.to_string())
+    ///     .err_filter(|&p| p >= 1024, |p| format!("port {} is privileged", p));
+    /// assert_eq!(Err("port 80 is privileged".to_string()), port);
+    /// ```
+    ///
+    /// [`fn_try!`]: macro.fn_try.html
+    fn err_filter<P, F>(self, pred: P, err_fn: F) -> Result<T, E>
+    where
+        P: FnOnce(&T) -> bool,
+        F: FnOnce(T) -> E;
+
+    /// This method converts `self` into an `Option`, like `Result::ok`, but
+    /// passes the error to `log_fn` before discarding it. `log_fn` is only called
+    /// if `self` is an `Err`. This is useful for fire-and-forget conversions,
+    /// where an error should not abort the computation, but should not go unnoticed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use fn_block::*;
+    /// let mut warnings = Vec::new();
+    /// let port = "http".parse::<u16>().ok_log_err(|e| warnings.push(format!("invalid port: {}", e)));
+    /// assert_eq!(None, port);
+    /// assert_eq!(vec!["invalid port: invalid digit found in string"], warnings);
+    /// ```
+    fn ok_log_err<F>(self, log_fn: F) -> Option<T>
+    where
+        F: FnOnce(E);
+
+    /// This method works like `Result::and_then`, but converts the errors of `self`
+    /// and of the result of `op` into the common error type `F` via `From`. It is
+    /// equivalent to `self.map_err_into().and_then(|v| op(v).map_err(Into::into))`
+    /// and centralizes the error conversion when chaining steps with different
+    /// error types.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use fn_block::*;
+    /// use std::num::ParseIntError;
+    /// use std::str::{from_utf8, Utf8Error};
+    /// #[derive(Debug, PartialEq)]
+    /// enum InputErr { Encoding, Number }
+    /// impl From<Utf8Error> for InputErr {
+    ///     fn from(_: Utf8Error) -> InputErr { InputErr::Encoding }
+    /// }
+    /// impl From<ParseIntError> for InputErr {
+    ///     fn from(_: ParseIntError) -> InputErr { InputErr::Number }
+    /// }
+    /// let bytes: &[u8] = b"42";
+    /// let res: Result<u32, InputErr> = from_utf8(bytes).and_then_into(|s| s.parse::<u32>());
+    /// assert_eq!(Ok(42), res);
+    /// ```
+    fn and_then_into<U, E2, F, O>(self, op: O) -> Result<U, F>
+    where
+        O: FnOnce(T) -> Result<U, E2>,
+        F: From<E> + From<E2>;
+}
+
+/// Implementation of trait `ResultExt` for
+/// all `Result` types.
+impl<T, E> ResultExt<T, E> for Result<T, E> {
+    fn map_err_into<F>(self) -> Result<T, F>
+    where
+        F: From<E>,
+    {
+        self.map_err(From::from)
+    }
+
+    fn contains_ok(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        match *self {
+            Ok(ref v) => v == x,
+            Err(_) => false,
+        }
+    }
+
+    fn contains_err(&self, e: &E) -> bool
+    where
+        E: PartialEq,
+    {
+        match *self {
+            Err(ref err) => err == e,
+            Ok(_) => false,
+        }
+    }
+
+    fn err_filter<P, F>(self, pred: P, err_fn: F) -> Result<T, E>
+    where
+        P: FnOnce(&T) -> bool,
+        F: FnOnce(T) -> E,
+    {
+        match self {
+            Ok(v) => {
+                if pred(&v) {
+                    Ok(v)
+                } else {
+                    Err(err_fn(v))
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn ok_log_err<F>(self, log_fn: F) -> Option<T>
+    where
+        F: FnOnce(E),
+    {
+        match self {
+            Ok(v) => Some(v),
+            Err(e) => {
+                log_fn(e);
+                None
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn and_then_into<U, E2, F, O>(self, op: O) -> Result<U, F>
+    where
+        O: FnOnce(T) -> Result<U, E2>,
+        F: From<E> + From<E2>,
+    {
+        match self {
+            Ok(v) => op(v).map_err(From::from),
+            Err(e) => Err(From::from(e)),
+        }
+    }
+}
+
+/// This trait, which is implemented for nested `Result` types with the same
+/// error type, provides the method `flatten_ok`, which removes one level of nesting:
+/// an `Ok` holding an inner `Result` is replaced by the inner `Result`, while
+/// an outer `Err` is kept. This is equivalent to the unstable `Result::flatten`.
+/// Nested results typically occur when mapping a `Result` with a fallible function,
+/// and flattening them allows using a single terminal `?`.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// use std::num::ParseIntError;
+/// let input: Result<&str, ParseIntError> = Ok("42");
+/// let res = fn_expr!{ Result<u32, ParseIntError>:
+///     input.map(|s| s.parse::<u32>()).flatten_ok()?.into_ok()
+/// };
+/// assert_eq!(Ok(42), res);
+/// ```
+pub trait FlattenOk<T, E> {
+    /// This method returns the inner `Result`, or the outer error if `self` is an `Err`.
+    fn flatten_ok(self) -> Result<T, E>;
+}
+
+/// Implementation of trait `FlattenOk` for
+/// all `Result<Result<T, E>, E>` types.
+impl<T, E> FlattenOk<T, E> for Result<Result<T, E>, E> {
+    #[inline(always)]
+    fn flatten_ok(self) -> Result<T, E> {
+        self.and_then(|inner| inner)
+    }
+}
+
+/// This trait, which is implemented for `Option`, provides the method
+/// `ok_or_default`, which converts the `Option` into a `Result`, using the
+/// default value of the error type if called on a `None`. The default error
+/// is only created if needed. This is particularly useful inside of a
+/// [`fn_expr!`] with a declared `Result` type, where the error type is inferred.
+///
+/// # Example using `fn_expr!`:
+///
+/// ```rust
+/// # #[macro_use]
+/// # use fn_block::*;
+/// #[derive(Debug, Default, PartialEq)]
+/// struct NotFound;
+///
+/// let o: Option<&str> = None;
+/// let res = fn_expr!{ Result<String, NotFound>:
+///     o.ok_or_default()?.to_uppercase().into_ok()
+/// };
+/// assert_eq!(Err(NotFound), res);
+/// ```
+///
+/// [`fn_expr!`]: macro.fn_expr.html
+pub trait OkOrDefault<T, E>
+where
+    E: Default,
+{
+    /// This method returns `Ok` holding the value wrapped in `self`, if `self`
+    /// is a `Some`, otherwise an `Err` holding `E::default()`.
+    fn ok_or_default(self) -> Result<T, E>;
+}
+
+/// Implementation of trait `OkOrDefault` for
+/// all `Option` types.
+impl<T, E> OkOrDefault<T, E> for Option<T>
+where
+    E: Default,
+{
+    fn ok_or_default(self) -> Result<T, E> {
+        self.ok_or_else(E::default)
+    }
+}
+
+/// This trait, which is implemented for `Option`, provides the method
+/// `ok_or_none`, which wraps the value of a `Some` into an `Ok`, keeping the
+/// `Option` around it. A `None` stays `None`. The error type is not
+/// determined by the `Option` and is left to inference, which is useful
+/// when feeding an optional value into a context expecting an
+/// `Option<Result<T, E>>`, e.g. when chaining it with an iterator of results.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// # use std::num::ParseIntError;
+/// let parsed: Vec<Result<u32, ParseIntError>> = vec!["1".parse(), "2".parse()];
+/// let all: Vec<u32> = parsed.into_iter()
+///     .chain(Some(3).ok_or_none())
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(vec![1, 2, 3], all);
+/// ```
+pub trait OkOrNone<T, E> {
+    /// This method returns `Some(Ok(t))` if `self` is `Some(t)`, otherwise `None`.
+    fn ok_or_none(self) -> Option<Result<T, E>>;
+}
+
+/// Implementation of trait `OkOrNone` for
+/// all `Option` types.
+impl<T, E> OkOrNone<T, E> for Option<T> {
+    fn ok_or_none(self) -> Option<Result<T, E>> {
+        self.map(Ok)
+    }
+}
+
+/// This trait, which is implemented for `Option` and `Result`, provides the method
+/// `none_to_err`, which converts a `None` into a `Result::Err` holding the error
+/// produced by the given function, while passing through a `Result` unchanged.
+/// This allows treating both types alike in front of a `?` operator, which is
+/// how the [`fn_expr_res!`] macro and the `missing_to(<error>):` form of
+/// [`fn_try!`] support mixing `Option` and `Result` values in one expression.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// #[derive(Debug, PartialEq)]
+/// struct Missing;
+///
+/// assert_eq!(Err(Missing), None::<u32>.none_to_err(|| Missing));
+/// assert_eq!(Ok(42), Some(42).none_to_err(|| Missing));
+/// assert_eq!(Err("failed"), Err::<u32, _>("failed").none_to_err(|| Missing));
+/// ```
+///
+/// [`fn_expr_res!`]: macro.fn_expr_res.html
+/// [`fn_try!`]: macro.fn_try.html
+pub trait OptionToResult<F> {
+    /// The `Result` type `self` is converted into.
+    type Output;
+
+    /// This method converts a `None` into an `Err` holding the result of `missing`
+    /// and a `Some` into an `Ok`. A `Result` is returned unchanged, without calling `missing`.
+    fn none_to_err(self, missing: F) -> Self::Output;
+}
+
+/// Implementation of trait `OptionToResult` for
+/// all `Option` types.
+impl<T, M, F> OptionToResult<F> for Option<T>
+where
+    F: FnOnce() -> M,
+{
+    type Output = Result<T, M>;
+
+    #[inline]
+    fn none_to_err(self, missing: F) -> Result<T, M> {
+        self.ok_or_else(missing)
+    }
+}
+
+/// Implementation of trait `OptionToResult` for
+/// all `Result` types.
+impl<T, E, F> OptionToResult<F> for Result<T, E> {
+    type Output = Result<T, E>;
+
+    #[inline]
+    fn none_to_err(self, _: F) -> Result<T, E> {
+        self
+    }
+}
+
+/// This trait, which is implemented for `Option` and `Result`, provides the method
+/// `unwrap_or_log`, which returns the wrapped value, or the default value of the
+/// value type after emitting a warning via the [`log`] crate. Other than
+/// `unwrap_or_default`, the fallback is not silent: the given message is logged,
+/// for a `Result` together with the `Debug` representation of the error.
+/// - *Note*: This trait is only available if the crate feature "log" is enabled.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// let port: u16 = "x".parse::<u16>().unwrap_or_log("invalid port, using default");
+/// assert_eq!(0, port);
+/// let name: String = None.unwrap_or_log("no name given");
+/// assert_eq!("", name);
+/// ```
+///
+/// [`log`]: https://crates.io/crates/log
+#[cfg(feature = "log")]
+pub trait UnwrapOrLog<T> {
+    /// This method returns the wrapped value, or logs a warning with the given
+    /// message and returns `T::default()`.
+    fn unwrap_or_log(self, msg: &str) -> T
+    where
+        T: Default;
+}
+
+/// Implementation of trait `UnwrapOrLog` for
+/// all `Option` types.
+#[cfg(feature = "log")]
+impl<T> UnwrapOrLog<T> for Option<T> {
+    fn unwrap_or_log(self, msg: &str) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Some(v) => v,
+            None => {
+                ::log::warn!("{}", msg);
+                T::default()
+            }
+        }
+    }
+}
+
+/// Implementation of trait `UnwrapOrLog` for
+/// all `Result` types with an error type implementing `Debug`.
+#[cfg(feature = "log")]
+impl<T, E> UnwrapOrLog<T> for Result<T, E>
+where
+    E: ::core::fmt::Debug,
+{
+    fn unwrap_or_log(self, msg: &str) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(v) => v,
+            Err(e) => {
+                ::log::warn!("{}: {:?}", msg, e);
+                T::default()
+            }
+        }
+    }
+}
+
+/// This trait, which is implemented for `Option` and `Result`, provides the method
+/// `unwrap_or_default_ext`, which returns the wrapped value, or the default value of
+/// the value type if there is none. Other than the inherent `unwrap_or_default` methods
+/// of `Option` and `Result`, the trait allows writing generic code over both types.
+/// The `_ext` suffix avoids a collision with the inherent methods.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// fn value_or_default<O: UnwrapOrDefault<u32>>(o: O) -> u32 {
+///     o.unwrap_or_default_ext()
+/// }
+/// assert_eq!(42, value_or_default(Some(42)));
+/// assert_eq!(0, value_or_default("x".parse::<u32>()));
+/// ```
+pub trait UnwrapOrDefault<T>
+where
+    T: Default,
+{
+    /// This method returns the wrapped value, or `T::default()`.
+    fn unwrap_or_default_ext(self) -> T;
+}
+
+/// Implementation of trait `UnwrapOrDefault` for
+/// all `Option` types.
+impl<T> UnwrapOrDefault<T> for Option<T>
+where
+    T: Default,
+{
+    fn unwrap_or_default_ext(self) -> T {
+        self.unwrap_or_default()
+    }
+}
+
+/// Implementation of trait `UnwrapOrDefault` for
+/// all `Result` types.
+impl<T, E> UnwrapOrDefault<T> for Result<T, E>
+where
+    T: Default,
+{
+    fn unwrap_or_default_ext(self) -> T {
+        self.unwrap_or_default()
+    }
+}
+
+/// This trait, which is implemented for `Option` and `Result`, provides the method
+/// `unwrap_or_panic_with`, which returns the wrapped value, or panics with the given
+/// message. It is intended as terminal call of a chain which is known to succeed and
+/// is available for both types via one import. For a `Result`, the panic message
+/// is followed by the `Debug` representation of the error, just like for `Result::expect`.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// let port = "8080".parse::<u16>().unwrap_or_panic_with("port is a valid number");
+/// assert_eq!(8080, port);
+/// let host = Some("localhost").unwrap_or_panic_with("host is configured");
+/// assert_eq!("localhost", host);
+/// ```
+pub trait UnwrapOrPanic<T> {
+    /// This method returns the wrapped value, or panics with the message `msg`.
+    fn unwrap_or_panic_with(self, msg: &str) -> T;
+}
+
+/// Implementation of trait `UnwrapOrPanic` for
+/// all `Option` types.
+impl<T> UnwrapOrPanic<T> for Option<T> {
+    #[track_caller]
+    fn unwrap_or_panic_with(self, msg: &str) -> T {
+        self.expect(msg)
+    }
+}
+
+/// Implementation of trait `UnwrapOrPanic` for
+/// all `Result` types with an error type implementing `Debug`.
+impl<T, E> UnwrapOrPanic<T> for Result<T, E>
+where
+    E: ::core::fmt::Debug,
+{
+    #[track_caller]
+    fn unwrap_or_panic_with(self, msg: &str) -> T {
+        self.expect(msg)
+    }
+}
+
+/// This trait defines how a context string is attached to an error type.
+/// It has to be implemented by error types used with the [`fn_expr_ctx!`] macro,
+/// which attaches the context to the error of a failed expression.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// #[derive(Debug, PartialEq)]
+/// struct Failure(String);
+///
+/// impl AddContext for Failure {
+///     fn add_context(self, context: &str) -> Failure {
+///         Failure(format!("{}: {}", context, self.0))
+///     }
+/// }
+///
+/// let f = Failure("not found".to_string()).add_context("reading file");
+/// assert_eq!(Failure("reading file: not found".to_string()), f);
+/// ```
+///
+/// [`fn_expr_ctx!`]: macro.fn_expr_ctx.html
+pub trait AddContext {
+    /// This method returns `self` with the given context attached.
+    fn add_context(self, context: &str) -> Self;
+}
+
+/// This trait, which is implemented for all iterators over `Option` values,
+/// provides the method `partition_options`, which collects all values
+/// wrapped in a `Some` into a `Vec` and counts the `None` values.
+/// Other than `Iterator::partition`, no second `Vec` is allocated for
+/// the discarded `None` values.
+/// - *Note*: This trait is only available if the crate feature "std" is enabled, which it is by default.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// let inputs = vec!["1", "x", "3"];
+/// let (numbers, failed) = inputs.iter().map(|s| s.parse::<u32>().ok()).partition_options();
+/// assert_eq!(vec![1, 3], numbers);
+/// assert_eq!(1, failed);
+/// ```
+#[cfg(feature = "std")]
+pub trait PartitionOptions<T> {
+    /// Consumes the iterator and returns all `Some` values in iteration order,
+    /// together with the number of `None` values.
+    fn partition_options(self) -> (Vec<T>, usize);
+}
+
+/// Implementation of trait `PartitionOptions` for
+/// all iterators over `Option` values.
+#[cfg(feature = "std")]
+impl<T, I> PartitionOptions<T> for I
+where
+    I: Iterator<Item = Option<T>>,
+{
+    fn partition_options(self) -> (Vec<T>, usize) {
+        let mut values = Vec::with_capacity(self.size_hint().0);
+        let mut none_count = 0;
+        for o in self {
+            match o {
+                Some(v) => values.push(v),
+                None => none_count += 1,
+            }
+        }
+        (values, none_count)
+    }
+}
+
+/// This trait, which is implemented for all iterators over `Result` values,
+/// provides the method `partition_results`, which collects all values
+/// wrapped in an `Ok` into one `Vec` and all errors into another `Vec`.
+/// This is useful when processing a batch of values, where errors
+/// should be reported, but should not stop the processing.
+/// - *Note*: This trait is only available if the crate feature "std" is enabled, which it is by default.
+///
+/// # Example:
+///
+/// ```rust
+/// # use fn_block::*;
+/// let inputs = vec!["1", "x", "3"];
+/// let (numbers, errors) = inputs.iter().map(|s| s.parse::<u32>()).partition_results();
+/// assert_eq!(vec![1, 3], numbers);
+/// assert_eq!(1, errors.len());
+/// ```
+#[cfg(feature = "std")]
+pub trait PartitionResults<T, E> {
+    /// Consumes the iterator and returns all `Ok` values and all `Err` values,
+    /// each in iteration order.
+    fn partition_results(self) -> (Vec<T>, Vec<E>);
+}
+
+/// Implementation of trait `PartitionResults` for
+/// all iterators over `Result` values.
+#[cfg(feature = "std")]
+impl<T, E, I> PartitionResults<T, E> for I
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    fn partition_results(self) -> (Vec<T>, Vec<E>) {
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+        for r in self {
+            match r {
+                Ok(v) => values.push(v),
+                Err(e) => errors.push(e),
+            }
+        }
+        (values, errors)
+    }
+}
+
+//////////////////////
+// Type definitions //
+//////////////////////
+
+/// The failure of an expression evaluated by the `timeout(<duration>):` form of
+/// [`fn_try!`]. Either the expression raised an error, or it did not finish in time.
+/// - *Note*: This type is an unstable API to make use of it, enable the crate feature "unproven".
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[cfg(feature = "unproven")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Caught<E> {
+    /// The expression raised the contained error.
+    Error(E),
+    /// The expression did not finish before the deadline.
+    Timeout,
+}
+
+/// The backtrace bound in the `catch (<error>, <trace>)` form of [`fn_try!`].
+/// Since the crate feature `backtrace` is enabled, this is the standard library's
+/// backtrace type.
+/// - *Note*: This type is an unstable API to make use of it, enable the crate feature "unproven".
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[cfg(all(feature = "unproven", feature = "backtrace"))]
+pub type CaughtBacktrace = ::std::backtrace::Backtrace;
+
+/// The backtrace bound in the `catch (<error>, <trace>)` form of [`fn_try!`].
+/// Since the crate feature `backtrace` is not enabled, this is a zero sized placeholder,
+/// which is displayed as a note that backtraces are disabled.
+/// - *Note*: This type is an unstable API to make use of it, enable the crate feature "unproven".
+///
+/// [`fn_try!`]: macro.fn_try.html
+#[cfg(all(feature = "unproven", not(feature = "backtrace")))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CaughtBacktrace;
+
+#[cfg(all(feature = "unproven", not(feature = "backtrace")))]
+impl ::core::fmt::Display for CaughtBacktrace {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.write_str("backtrace capturing disabled")
+    }
+}
+
+/// Items used by the expansions of the macros in this crate.
+/// These are not part of the public API and may change at any time.
+#[doc(hidden)]
+pub mod __private {
+    pub use core::default::Default;
+    pub use core::ops::ControlFlow;
+    #[cfg(feature = "log")]
+    pub use log;
+    #[cfg(feature = "tracing")]
+    pub use tracing;
+
+    /// Turns the coroutine created by `fn_gen!` into an iterator over its yielded values.
+    #[cfg(all(feature = "unproven", feature = "nightly-coroutines"))]
+    #[inline]
+    pub fn gen_iter<G>(coroutine: G) -> impl Iterator<Item = G::Yield>
+    where
+        G: core::ops::Coroutine<Return = ()> + Unpin,
+    {
+        core::iter::from_coroutine(coroutine)
+    }
+
+    /// Calls the closure of `fn_expr_ref!`, requiring it to only borrow its captures.
+    #[inline(always)]
+    pub fn call_ref<R, F>(f: F) -> R
+    where
+        F: Fn() -> R,
+    {
+        f()
+    }
+
+    /// Distinguishes successful candidates of `fn_search!`.
+    pub trait SearchHit {
+        fn is_hit(&self) -> bool;
+    }
+
+    impl<T> SearchHit for Option<T> {
+        #[inline]
+        fn is_hit(&self) -> bool {
+            self.is_some()
+        }
+    }
+
+    impl<T, E> SearchHit for Result<T, E> {
+        #[inline]
+        fn is_hit(&self) -> bool {
+            self.is_ok()
+        }
+    }
+
+    /// Converts a tuple of `Option` values into an `Option` of a tuple of all values,
+    /// if all of them are a `Some`. Used by the aggregating form of `fn_try_all!`.
+    #[cfg(feature = "unproven")]
+    pub trait AllSome {
+        type Output;
+        fn all_some(self) -> Option<Self::Output>;
+    }
+
+    #[cfg(feature = "unproven")]
+    macro_rules! impl_all_some {
+        ($($name:ident),+) => {
+            impl<$($name),+> AllSome for ($(Option<$name>,)+) {
+                type Output = ($($name,)+);
+                #[inline]
+                #[allow(non_snake_case)]
+                fn all_some(self) -> Option<Self::Output> {
+                    let ($($name,)+) = self;
+                    Some(($($name?,)+))
+                }
+            }
+        };
+    }
+
+    #[cfg(feature = "unproven")]
+    impl_all_some!(A, B);
+    #[cfg(feature = "unproven")]
+    impl_all_some!(A, B, C);
+    #[cfg(feature = "unproven")]
+    impl_all_some!(A, B, C, D);
+    #[cfg(feature = "unproven")]
+    impl_all_some!(A, B, C, D, E);
+    #[cfg(feature = "unproven")]
+    impl_all_some!(A, B, C, D, E, F);
+    #[cfg(feature = "unproven")]
+    impl_all_some!(A, B, C, D, E, F, G);
+    #[cfg(feature = "unproven")]
+    impl_all_some!(A, B, C, D, E, F, G, H);
+
+    /// Attaches the context of `fn_expr_ctx!` to the error of the given result.
+    #[inline]
+    pub fn with_context<T, E>(res: Result<T, E>, context: &str) -> Result<T, E>
+    where
+        E: ::AddContext,
+    {
+        res.map_err(|e| e.add_context(context))
+    }
+
+    /// Calls the hook of the `on_error` clause of `fn_try!` with the given error,
+    /// ignoring the hook's result.
+    #[cfg(feature = "unproven")]
+    #[inline]
+    pub fn on_error<E, R, F>(e: &E, hook: F)
+    where
+        F: FnOnce(&E) -> R,
+    {
+        hook(e);
+    }
+
+    /// Captures the backtrace for the `catch (<error>, <trace>)` form of `fn_try!`.
+    #[cfg(all(feature = "unproven", feature = "backtrace"))]
+    #[inline]
+    pub fn capture_backtrace() -> ::CaughtBacktrace {
+        ::std::backtrace::Backtrace::capture()
+    }
+
+    /// Creates the placeholder for the `catch (<error>, <trace>)` form of `fn_try!`,
+    /// since the crate feature `backtrace` is not enabled.
+    #[cfg(all(feature = "unproven", not(feature = "backtrace")))]
+    #[inline]
+    pub fn capture_backtrace() -> ::CaughtBacktrace {
+        ::CaughtBacktrace
+    }
+
+    /// Conversion of the values of `fn_main_try!` into an exit code.
+    #[cfg(all(feature = "unproven", feature = "std"))]
+    pub trait ExitCodeValue {
+        fn exit_code(self) -> ::std::process::ExitCode;
+    }
+
+    #[cfg(all(feature = "unproven", feature = "std"))]
+    impl ExitCodeValue for () {
+        #[inline]
+        fn exit_code(self) -> ::std::process::ExitCode {
+            ::std::process::ExitCode::SUCCESS
+        }
+    }
+
+    #[cfg(all(feature = "unproven", feature = "std"))]
+    impl ExitCodeValue for u8 {
+        #[inline]
+        fn exit_code(self) -> ::std::process::ExitCode {
+            ::std::process::ExitCode::from(self)
+        }
+    }
+
+    #[cfg(all(feature = "unproven", feature = "std"))]
+    impl ExitCodeValue for i32 {
+        #[inline]
+        fn exit_code(self) -> ::std::process::ExitCode {
+            match <u8 as ::std::convert::TryFrom<i32>>::try_from(self) {
+                Ok(code) => ::std::process::ExitCode::from(code),
+                Err(_) => ::std::process::ExitCode::FAILURE,
+            }
+        }
+    }
+
+    #[cfg(all(feature = "unproven", feature = "std"))]
+    impl ExitCodeValue for ::std::process::ExitCode {
+        #[inline]
+        fn exit_code(self) -> ::std::process::ExitCode {
+            self
+        }
+    }
+
+    /// Evaluates the given function on a helper thread and waits at most `timeout`
+    /// for its result. Used by the `timeout(<duration>):` form of `fn_try!`.
+    /// A panic of the function is resumed on the calling thread.
+    #[cfg(all(feature = "unproven", feature = "std"))]
+    pub fn run_with_timeout<T, E, F>(timeout: ::std::time::Duration, f: F) -> Result<T, ::Caught<E>>
+    where
+        F: FnOnce() -> Result<T, E> + Send + 'static,
+        T: Send + 'static,
+        E: Send + 'static,
+    {
+        use std::sync::mpsc::{channel, RecvTimeoutError};
+        let (sender, receiver) = channel();
+        let worker = ::std::thread::spawn(move || {
+            // the receiver may be gone after a timeout, the result is discarded then
+            let _ = sender.send(f());
+        });
+        match receiver.recv_timeout(timeout) {
+            Ok(result) => result.map_err(::Caught::Error),
+            Err(RecvTimeoutError::Timeout) => Err(::Caught::Timeout),
+            Err(RecvTimeoutError::Disconnected) => match worker.join() {
+                Err(panic) => ::std::panic::resume_unwind(panic),
+                Ok(()) => unreachable!("worker finished without sending a result"),
+            },
+        }
+    }
+
+    /// Stable entry point to `Try::from_output` for the `as <Type>:` form of `fn_try!`,
+    /// so that crates only implementing the `Try` trait need the unstable feature.
+    #[cfg(all(feature = "unproven", feature = "nightly-try-trait"))]
+    #[inline]
+    pub fn from_output<T: ::core::ops::Try>(output: T::Output) -> T {
+        T::from_output(output)
+    }
+
+    /// Stable entry point to `Try::branch` for the `as <Type>:` form of `fn_try!`.
+    #[cfg(all(feature = "unproven", feature = "nightly-try-trait"))]
+    #[inline]
+    pub fn branch<T: ::core::ops::Try>(t: T) -> ::core::ops::ControlFlow<T::Residual, T::Output> {
+        t.branch()
+    }
+
+    /// Error type of `fn_try_or!` and of `fn_try_opt!` without pinned error type, discarding the original
+    /// error. This type must not implement `Debug` itself, otherwise the `From`
+    /// implementation below would overlap with `From<T> for T`.
+    #[cfg(feature = "unproven")]
+    pub struct DiscardedError;
+
+    #[cfg(feature = "unproven")]
+    impl<E: ::core::fmt::Debug> From<E> for DiscardedError {
+        #[inline]
+        fn from(_: E) -> DiscardedError {
+            DiscardedError
+        }
+    }
+
+    /// Error type of `fn_try_expect!` and of `fn_try_opt!` with `log` modifier and
+    /// without pinned error type, holding the `Debug` representation of the original error. This type must not
+    /// implement `Debug` itself, otherwise the `From` implementation below would overlap
+    /// with `From<T> for T`.
+    #[cfg(feature = "unproven")]
+    pub struct DebuggedError(::alloc::string::String);
+
+    #[cfg(feature = "unproven")]
+    impl<E: ::core::fmt::Debug> From<E> for DebuggedError {
+        fn from(e: E) -> DebuggedError {
+            DebuggedError(::alloc::format!("{:?}", e))
+        }
+    }
+
+    #[cfg(feature = "unproven")]
+    impl ::core::fmt::Display for DebuggedError {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    /// Error type of the `catch display` form of `fn_try!`, holding the formatted
+    /// message of the original error. This type must not implement `Display` itself,
+    /// otherwise the `From` implementation below would overlap with `From<T> for T`.
+    #[cfg(feature = "unproven")]
+    pub struct DisplayedError(::alloc::string::String);
+
+    #[cfg(feature = "unproven")]
+    impl<E: ::core::fmt::Display> From<E> for DisplayedError {
+        fn from(e: E) -> DisplayedError {
+            DisplayedError(::alloc::string::ToString::to_string(&e))
+        }
+    }
+
+    #[cfg(feature = "unproven")]
+    impl DisplayedError {
+        /// Passes the formatted message to the given handler and returns its result.
+        #[inline]
+        pub fn handle<T, F>(self, handler: F) -> T
+        where
+            F: FnOnce(::alloc::string::String) -> T,
+        {
+            handler(self.0)
+        }
+    }
 }
 
 #[macro_use]
@@ -1,40 +1,27 @@
 use super::*;
-
-/// Trait implemented for all sized types, providing a `when` function,
-/// wrapping the element it is called on into an `Optional::Some` if the
-/// given predicate holds true for the value and `Optional::None` otherwise.
-trait Optionalize: Sized {
-    fn when<F>(self, predicate: F) -> Option<Self>
-    where
-        F: FnOnce(&Self) -> bool,
-    {
-        if predicate(&self) {
-            Some(self)
-        } else {
-            None
-        }
-    }
-}
-
-/// Implementation of Optionalize for all values
-impl<T> Optionalize for T {}
+#[cfg(not(feature = "std"))]
+use std::prelude::v1::{Box, String, ToString, Vec, format, vec};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
 
 #[test]
+#[allow(deprecated)]
 fn fn_block_some() {
     let o: Option<i32> = Some(42);
     let foo = fn_block!{{
-        let gt_zero = o?.when(|&i| i > 0);
-        gt_zero?.when(|&i| i%2 == 0)
+        let gt_zero = o?.some_if(|&i| i > 0);
+        gt_zero?.some_if(|&i| i%2 == 0)
     }};
     assert_eq!(42, foo.expect("result"));
 }
 
 #[test]
+#[allow(deprecated)]
 fn fn_block_none() {
     let o: Option<i32> = Some(42);
     let foo = fn_block!{{
-        let big_num = o?.when(|&i| i > 1000);
-        big_num?.when(|&i| i < 2000)
+        let big_num = o?.some_if(|&i| i > 1000);
+        big_num?.some_if(|&i| i < 2000)
     }};
     assert!(foo.is_none());
 }
@@ -43,8 +30,8 @@ fn fn_block_none() {
 fn fn_expr_block_some() {
     let o: Option<i32> = Some(42);
     let foo = fn_expr!{{
-        let gt_zero = o?.when(|&i| i > 0);
-        gt_zero?.when(|&i| i%2 == 0)
+        let gt_zero = o?.some_if(|&i| i > 0);
+        gt_zero?.some_if(|&i| i%2 == 0)
     }};
     assert_eq!(42, foo.expect("result"));
 }
@@ -53,8 +40,8 @@ fn fn_expr_block_some() {
 fn fn_expr_block_none() {
     let o: Option<i32> = Some(42);
     let foo = fn_expr!{{
-        let big_num = o?.when(|&i| i > 1000);
-        big_num?.when(|&i| i < 2000)
+        let big_num = o?.some_if(|&i| i > 1000);
+        big_num?.some_if(|&i| i < 2000)
     }};
     assert!(foo.is_none());
 }
@@ -62,18 +49,19 @@ fn fn_expr_block_none() {
 #[test]
 fn fn_expr_some() {
     let o: Option<i32> = Some(42);
-    let foo = fn_expr!{ o?.when(|&i| i > 0)?.when(|&i| i%2 == 0) };
+    let foo = fn_expr!{ o?.some_if(|&i| i > 0)?.some_if(|&i| i%2 == 0) };
     assert_eq!(42, foo.expect("result"));
 }
 
 #[test]
 fn fn_expr_none() {
     let o: Option<i32> = Some(42);
-    let foo = fn_expr!{ o?.when(|&i| i > 1000)?.when(|&i| i < 2000) };
+    let foo = fn_expr!{ o?.some_if(|&i| i > 1000)?.some_if(|&i| i < 2000) };
     assert!(foo.is_none());
 }
 
 #[test]
+#[allow(deprecated, clippy::redundant_closure_call)]
 fn fn_block_resulttype() {
     use std::num::ParseIntError;
     let res = fn_block!{ Result<u32,ParseIntError>: {
@@ -84,12 +72,68 @@ fn fn_block_resulttype() {
 }
 
 #[test]
+#[allow(clippy::redundant_closure_call)]
 fn fn_expr_resulttype() {
     use std::num::ParseIntError;
     let res = fn_expr!{ Result<u32,ParseIntError>: "4711".parse() };
     assert_eq!(4711, res.unwrap());
 }
 
+#[test]
+fn fn_scope_block() {
+    let o: Option<i32> = Some(42);
+    let foo = fn_scope!{{
+        let gt_zero = o?.some_if(|&i| i > 0);
+        gt_zero?.some_if(|&i| i%2 == 0)
+    }};
+    assert_eq!(42, foo.expect("result"));
+}
+
+#[test]
+fn fn_scope_expr() {
+    let o: Option<i32> = Some(42);
+    let foo = fn_scope!{ o?.some_if(|&i| i > 1000)?.some_if(|&i| i < 2000) };
+    assert!(foo.is_none());
+}
+
+#[test]
+#[allow(clippy::redundant_closure_call)]
+fn fn_scope_resulttype() {
+    use std::num::ParseIntError;
+    let res = fn_scope!{ Result<u32,ParseIntError>: "4711".parse() };
+    assert_eq!(4711, res.unwrap());
+    let res = fn_scope!{ Result<u32,ParseIntError>: {
+        let s = "4711";
+        s.parse()
+    }};
+    assert_eq!(4711, res.unwrap());
+}
+
+#[test]
+#[allow(clippy::redundant_closure_call)]
+fn fn_expr_never_type_coercion() {
+    let o: Option<u32> = Some(42);
+    let i: u32 = match o {
+        Some(i) => i,
+        None => fn_expr!{ !: { panic!("unreachable") } },
+    };
+    assert_eq!(42, i);
+}
+
+#[test]
+#[should_panic(expected = "diverged")]
+#[allow(clippy::redundant_closure_call)]
+fn fn_expr_never_type_diverging() {
+    let _i: u32 = fn_expr!{ !: { panic!("diverged") } };
+}
+
+#[test]
+#[should_panic(expected = "diverged")]
+#[allow(deprecated, clippy::redundant_closure_call)]
+fn fn_block_never_type_diverging() {
+    let _s: String = fn_block!{ !: { panic!("diverged") } };
+}
+
 #[test]
 fn into_ok() {
     let r: Result<&str, u16> = "foo".into_ok();
@@ -102,68 +146,2319 @@ fn into_some() {
     assert_eq!(42, r.expect("result is Some"));
 }
 
+fn append_a(s: String) -> String {
+    s + "a"
+}
+
+fn append_b(s: String) -> String {
+    s + "b"
+}
+
 #[test]
-fn showcase() {
-    // Not an actual test, but a showcase for several alternative ways to express the same
-    let o = Some("Foobar");
-    let s = o.and_then(|st| st.get(0..3)).map(|st| st.to_lowercase());
-    assert_eq!("foo", s.expect("result is Some"));
+fn pipe_order() {
+    let s = String::new().pipe(append_a).pipe(append_b);
+    assert_eq!("ab", s);
+    let s = String::new().pipe(append_b).pipe(append_a);
+    assert_eq!("ba", s);
+}
 
-    let s = (|| Some(o?.get(0..3)?.to_lowercase()))();
-    assert_eq!("foo", s.expect("result is Some"));
+#[test]
+fn pipe_in_fn_expr() {
+    let o = Some("Foo");
+    let s = fn_expr!{ o?.to_lowercase().pipe(append_a).pipe(append_b).into_some() };
+    assert_eq!("fooab", s.expect("result is Some"));
+}
 
-    use super::IntoSome;
-    let s = fn_expr!{ o?.get(0..3)?.to_lowercase().into_some() };
-    assert_eq!("foo", s.expect("result is Some"));
+#[test]
+fn pipe_ref_and_pipe_mut() {
+    let o = Some(" Foo ");
+    let res = fn_expr!{{
+        let mut name = o?.trim().to_string();
+        let len = name.pipe_ref(String::len);
+        name.pipe_mut(|n| n.push_str("bar"));
+        (name, len).into_some()
+    }};
+    assert_eq!(Some(("Foobar".to_string(), 3)), res);
 }
 
-use std::num::ParseIntError;
-use std::str::Utf8Error;
+fn first_or_default<T: Default + Clone>(items: Vec<T>) -> T {
+    items.first().cloned().unwrap_or_default()
+}
 
-enum ConvertErr {
-    StrParseErr,
-    IntParseErr,
+#[test]
+fn pipe_generic_fn_and_closure() {
+    let i: u32 = vec![7u32, 8].pipe(first_or_default);
+    assert_eq!(7, i);
+    let s = Vec::<String>::new().pipe(first_or_default).pipe(|s| s + "x");
+    assert_eq!("x", s);
+    let res = fn_expr!{ Result<u32, std::num::ParseIntError>:
+        "21".pipe(str::parse::<u32>)?.pipe(|i| i * 2).into_ok()
+    };
+    assert_eq!(Ok(42), res);
 }
 
-impl From<Utf8Error> for ConvertErr {
-    fn from(_: Utf8Error) -> ConvertErr {
-        ConvertErr::StrParseErr
+#[test]
+fn flat_map_some_equivalence() {
+    let half = |i: u32| i.checked_sub(10);
+    for o in [Some(42), Some(7), None] {
+        assert_eq!(o.and_then(half), o.flat_map_some(half));
     }
 }
-impl From<ParseIntError> for ConvertErr {
-    fn from(_: ParseIntError) -> ConvertErr {
-        ConvertErr::IntParseErr
+
+#[test]
+fn flat_map_some_none_short_circuit() {
+    let mut called = false;
+    let r = None::<u32>.flat_map_some(|i| {
+        called = true;
+        Some(i)
+    });
+    assert!(r.is_none());
+    assert!(!called);
+}
+
+#[test]
+fn map_some_equivalence() {
+    for o in [Some(41), None] {
+        assert_eq!(o.map(|i| i + 1), o.map_some(|i| i + 1));
     }
 }
 
 #[test]
-#[cfg(feature = "unproven")]
-fn fn_catch_result() {
-    use std::str::from_utf8;
+fn map_some_or_equivalence() {
+    for o in [Some("foo"), None] {
+        assert_eq!(o.map_or(0, str::len), o.map_some_or(0, str::len));
+    }
+    assert_eq!(3, Some("foo").map_some_or(0, str::len));
+    assert_eq!(0, None.map_some_or(0, str::len));
+}
 
-    let s: &[u8] = &[0x0020, 0x0034, 0x0032];
-    let i = fn_try! {
-        from_utf8(s)?.trim().parse::<u32>()?
-        => catch {
-            ConvertErr::StrParseErr => 0u32,
-            ConvertErr::IntParseErr => u32::max_value()
+#[test]
+fn zip3_all_present() {
+    assert_eq!(Some((1, "b", 'c')), Some(1).zip3(Some("b"), Some('c')));
+}
+
+#[test]
+fn zip3_one_missing() {
+    assert_eq!(None, None::<u32>.zip3(Some("b"), Some('c')));
+    assert_eq!(None, Some(1).zip3(None::<&str>, Some('c')));
+    assert_eq!(None, Some(1).zip3(Some("b"), None::<char>));
+}
+
+#[test]
+fn zip4_all_present_and_missing() {
+    assert_eq!(Some((1, 2, 3, 4)), Some(1).zip4(Some(2), Some(3), Some(4)));
+    assert_eq!(None, Some(1).zip4(Some(2), Some(3), None::<u32>));
+}
+
+#[test]
+fn zip_all_short_circuit() {
+    let mut evaluated = false;
+    let r = zip_all!(Some(1), None::<u32>, {
+        evaluated = true;
+        Some(3)
+    });
+    assert_eq!(None, r);
+    assert!(!evaluated);
+    assert_eq!(Some((1, 2, 3)), zip_all!(Some(1), Some(2), Some(3)));
+}
+
+#[test]
+fn flat_map_ok_equivalence() {
+    let parse = |s: &str| s.parse::<u32>();
+    for r in [Ok("42"), Ok("x"), Err("x".parse::<u32>().unwrap_err())] {
+        assert_eq!(r.clone().and_then(parse), r.flat_map_ok(parse));
+    }
+}
+
+#[test]
+fn flat_map_ok_err_pass_through() {
+    let mut called = false;
+    let r: Result<u32, InnerErr> = Err::<u32, InnerErr>(InnerErr(7)).flat_map_ok(|i| {
+        called = true;
+        Ok(i + 1)
+    });
+    assert_eq!(Err(InnerErr(7)), r);
+    assert!(!called);
+}
+
+#[cfg(feature = "std")]
+fn lower_prefix(o: Option<&str>) -> Option<Cow<'_, str>> {
+    fn_expr!{{
+        let s = o?.get(0..3)?;
+        if s.chars().all(char::is_lowercase) {
+            s.into_cow_borrowed().into_some()
+        } else {
+            s.to_lowercase().into_cow_owned().into_some()
         }
+    }}
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_cow_borrowed() {
+    match lower_prefix(Some("foobar")) {
+        Some(Cow::Borrowed(s)) => assert_eq!("foo", s),
+        other => panic!("expected borrowed Cow, got {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_cow_owned() {
+    match lower_prefix(Some("FooBar")) {
+        Some(Cow::Owned(s)) => assert_eq!("foo", s),
+        other => panic!("expected owned Cow, got {:?}", other),
+    }
+    assert!(lower_prefix(None).is_none());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_cow_slice() {
+    let v = vec![1, 2, 3];
+    let borrowed: Cow<[u32]> = v[..2].into_cow_borrowed();
+    let owned = v.clone().into_cow_owned();
+    assert_eq!(&[1, 2], &*borrowed);
+    assert_eq!(Cow::Owned::<[u32]>(v), owned);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_cow_borrowing_str() {
+    let borrowed: Cow<str> = "foo".into_cow();
+    assert!(matches!(borrowed, Cow::Borrowed("foo")));
+    let owned: Cow<str> = "foo".to_uppercase().into_cow_owned();
+    assert!(matches!(owned, Cow::Owned(ref s) if s == "FOO"));
+    let o = Some(" bar ");
+    let res = fn_expr!{ o?.trim().into_cow().into_some() };
+    assert_eq!(Some(Cow::Borrowed("bar")), res);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_cow_borrowing_slice() {
+    let values = vec![1, 2, 3];
+    let borrowed: Cow<[u32]> = values[..2].into_cow();
+    assert!(matches!(borrowed, Cow::Borrowed(&[1, 2])));
+    let owned = values.clone().into_cow_owned();
+    assert!(matches!(owned, Cow::Owned(ref v) if v == &values));
+    let res = fn_expr!{ values.get(1..)?.into_cow().into_some() };
+    assert_eq!(Some(Cow::Borrowed(&[2, 3][..])), res);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_cow_borrowing_clone() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Config {
+        port: u16,
+    }
+    let configs = [Config { port: 80 }, Config { port: 8080 }];
+    let cow = fn_expr!{ configs.iter().find(|c| c.port > 1024)?.into_cow().into_some() };
+    assert!(matches!(cow, Some(Cow::Borrowed(&Config { port: 8080 }))));
+    let mut owned = cow.expect("found config");
+    owned.to_mut().port += 1;
+    assert_eq!(Config { port: 8081 }, owned.into_owned());
+    assert_eq!(8080, configs[1].port);
+}
+
+#[derive(Debug, PartialEq)]
+struct InnerErr(u32);
+
+#[derive(Debug, PartialEq)]
+struct OuterErr(String);
+
+impl From<InnerErr> for OuterErr {
+    fn from(e: InnerErr) -> OuterErr {
+        OuterErr(format!("inner {}", e.0))
+    }
+}
+
+#[test]
+fn map_err_into_ok() {
+    let r: Result<u32, OuterErr> = Ok::<u32, InnerErr>(42).map_err_into();
+    assert_eq!(Ok(42), r);
+}
+
+#[test]
+fn map_err_into_err() {
+    let r = Err::<u32, InnerErr>(InnerErr(7)).map_err_into::<OuterErr>();
+    assert_eq!(Err(OuterErr("inner 7".to_string())), r);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn partition_options_empty() {
+    let (values, none_count) = Vec::<Option<u32>>::new().into_iter().partition_options();
+    assert!(values.is_empty());
+    assert_eq!(0, none_count);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn partition_options_all_some() {
+    let (values, none_count) = vec![Some(3), Some(1), Some(2)].into_iter().partition_options();
+    assert_eq!(vec![3, 1, 2], values);
+    assert_eq!(0, none_count);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn partition_options_all_none() {
+    let (values, none_count) = vec![None::<u32>, None, None].into_iter().partition_options();
+    assert!(values.is_empty());
+    assert_eq!(3, none_count);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn partition_options_mixed() {
+    let inputs = vec![Some("c"), None, Some("a"), None, Some("b")];
+    let (values, none_count) = inputs.into_iter().partition_options();
+    assert_eq!(vec!["c", "a", "b"], values);
+    assert_eq!(2, none_count);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn partition_results_empty() {
+    let (values, errors) = Vec::<Result<u32, ()>>::new().into_iter().partition_results();
+    assert!(values.is_empty());
+    assert!(errors.is_empty());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn partition_results_order() {
+    let inputs = vec![Ok(3), Err("b"), Ok(1), Err("a"), Ok(2)];
+    let (values, errors) = inputs.into_iter().partition_results();
+    assert_eq!(vec![3, 1, 2], values);
+    assert_eq!(vec!["b", "a"], errors);
+}
+
+#[test]
+fn const_into_some_and_into_ok() {
+    use fns::{into_ok, into_some};
+    const X: Option<u32> = into_some(42);
+    const Y: Result<u32, ()> = into_ok(42);
+    assert_eq!(Some(42), X);
+    assert_eq!(Ok(42), Y);
+}
+
+#[test]
+fn some_if_eager() {
+    use fns::some_if;
+    assert_eq!(Some(42), some_if(42, |&i| i > 0));
+    assert_eq!(None, some_if(-42, |&i| i > 0));
+}
+
+#[test]
+fn some_if_lazy() {
+    use fns::some_if_with;
+    let mut created = 0;
+    assert_eq!(Some(42), some_if_with(|| { created += 1; 42 }, |&i| i > 0));
+    assert_eq!(None, some_if_with(|| { created += 1; -42 }, |&i| i > 0));
+    assert_eq!(2, created);
+}
+
+const HALF_MAX: u32 = const_fn_expr!{ u32: match u32::MAX.checked_div(2) {
+    Some(half) => half,
+    None => 0,
+}};
+
+#[test]
+fn const_fn_expr_initializer() {
+    assert_eq!(u32::MAX / 2, HALF_MAX);
+    let first = const_fn_expr!{ "foo".as_bytes()[0] };
+    assert_eq!(b'f', first);
+}
+
+#[test]
+fn fn_expr_some_wraps() {
+    let o = Some("Foobar");
+    let s: Option<String> = fn_expr_some!{ o?.get(0..3)?.to_lowercase() };
+    assert_eq!(Some("foo".to_string()), s);
+    let none: Option<&str> = None;
+    let s = fn_expr_some!{
+        let prefix = none?.get(0..3)?;
+        prefix.to_lowercase()
     };
-    assert_eq!(42, i);
+    assert_eq!(None, s);
 }
 
 #[test]
-#[cfg(feature = "unproven")]
-fn fn_catch_error() {
-    use std::str::from_utf8;
+fn fn_expr_ok_wraps() {
+    let ok: Result<u32, InnerErr> = fn_expr_ok!{ Ok::<u32, InnerErr>(21)? * 2 };
+    assert_eq!(Ok(42), ok);
+    let err: Result<u32, OuterErr> = fn_expr_ok!{ Err(InnerErr(1))? };
+    assert_eq!(Err(OuterErr("inner 1".to_string())), err);
+}
 
-    let s: &[u8] = &[0x0020, 0x005A, 0x0032];
-    let i = fn_try! {
-        from_utf8(s)?.trim().parse::<u32>()?
-        => catch {
-            ConvertErr::StrParseErr => 0u32,
-            ConvertErr::IntParseErr => u32::max_value()
+#[test]
+fn none_if_outcomes() {
+    use fns::none_if;
+    use std::rc::Rc;
+    assert_eq!(Some(3), none_if(3, |&n| n == 0));
+    let sentinel = Rc::new(0);
+    let res = none_if(sentinel.clone(), |n| **n == 0);
+    assert_eq!(None, res);
+    // the value has been dropped on the `None` branch
+    assert_eq!(1, Rc::strong_count(&sentinel));
+}
+
+#[test]
+fn ok_if_eager() {
+    use fns::ok_if;
+    assert_eq!(Ok(42), ok_if(42, |&i| i > 0, InnerErr(0)));
+    assert_eq!(Err(InnerErr(0)), ok_if(-42, |&i| i > 0, InnerErr(0)));
+}
+
+#[test]
+fn ok_if_lazy() {
+    use fns::ok_if_else;
+    let mut created = 0;
+    let r = ok_if_else(42, |&i| i > 0, || { created += 1; InnerErr(1) });
+    assert_eq!(Ok(42), r);
+    assert_eq!(0, created);
+    let r = ok_if_else(-42, |&i| i > 0, || { created += 1; InnerErr(1) });
+    assert_eq!(Err(InnerErr(1)), r);
+    assert_eq!(1, created);
+}
+
+#[test]
+fn err_if_eager() {
+    use fns::err_if;
+    assert_eq!(Err(InnerErr(0)), err_if(42, |&i| i > 0, InnerErr(0)));
+    assert_eq!(Ok(-42), err_if(-42, |&i| i > 0, InnerErr(0)));
+}
+
+#[test]
+fn err_if_lazy() {
+    use fns::err_if_else;
+    let r = err_if_else(|| "".to_string(), |s| s.is_empty(), InnerErr(1));
+    assert_eq!(Err(InnerErr(1)), r);
+    let r = err_if_else(|| "foo".to_string(), |s| s.is_empty(), InnerErr(1));
+    assert_eq!(Ok("foo".to_string()), r);
+}
+
+#[test]
+fn option_contains() {
+    assert!(Some(42).contains(&42));
+    assert!(!Some(42).contains(&7));
+    assert!(!None::<u32>.contains(&42));
+}
+
+#[test]
+fn result_contains() {
+    let ok: Result<u32, InnerErr> = Ok(42);
+    let err: Result<u32, InnerErr> = Err(InnerErr(7));
+    assert!(ok.contains_ok(&42));
+    assert!(!ok.contains_ok(&7));
+    assert!(!ok.contains_err(&InnerErr(42)));
+    assert!(err.contains_err(&InnerErr(7)));
+    assert!(!err.contains_err(&InnerErr(42)));
+    assert!(!err.contains_ok(&7));
+}
+
+static DEFAULT_ERRS: ::std::sync::atomic::AtomicUsize = ::std::sync::atomic::AtomicUsize::new(0);
+
+#[derive(Debug, PartialEq)]
+struct DefaultErr;
+
+impl Default for DefaultErr {
+    fn default() -> DefaultErr {
+        DEFAULT_ERRS.fetch_add(1, ::std::sync::atomic::Ordering::SeqCst);
+        DefaultErr
+    }
+}
+
+#[test]
+fn ok_or_default_lazy() {
+    use std::sync::atomic::Ordering;
+    let res: Result<u32, DefaultErr> = Some(42).ok_or_default();
+    assert_eq!(Ok(42), res);
+    assert_eq!(0, DEFAULT_ERRS.load(Ordering::SeqCst));
+    let res = fn_expr!{ Result<u32, DefaultErr>: None::<u32>.ok_or_default()?.into_ok() };
+    assert_eq!(Err(DefaultErr), res);
+    assert_eq!(1, DEFAULT_ERRS.load(Ordering::SeqCst));
+}
+
+#[test]
+fn ok_or_none() {
+    use std::num::ParseIntError;
+    let some: Option<Result<u32, ParseIntError>> = Some(42).ok_or_none();
+    assert_eq!(Some(Ok(42)), some);
+    let none: Option<Result<u32, ParseIntError>> = None.ok_or_none();
+    assert_eq!(None, none);
+}
+
+fn lengths_or_default<O: UnwrapOrDefault<String>>(items: Vec<O>) -> Vec<usize> {
+    items.into_iter().map(|o| o.unwrap_or_default_ext().len()).collect()
+}
+
+#[test]
+fn unwrap_or_default_ext() {
+    assert_eq!(42, Some(42).unwrap_or_default_ext());
+    assert_eq!(0, None::<u32>.unwrap_or_default_ext());
+    assert_eq!(42, Ok::<u32, InnerErr>(42).unwrap_or_default_ext());
+    assert_eq!(0, Err::<u32, _>(InnerErr(1)).unwrap_or_default_ext());
+    assert_eq!(vec![3, 0], lengths_or_default(vec![Some("foo".to_string()), None]));
+    assert_eq!(vec![0, 3], lengths_or_default(vec![Err(InnerErr(1)), Ok("bar".to_string())]));
+}
+
+#[test]
+fn unwrap_or_panic_with_success() {
+    assert_eq!(42, Some(42).unwrap_or_panic_with("value present"));
+    assert_eq!(42, Ok::<u32, InnerErr>(42).unwrap_or_panic_with("lookup succeeds"));
+}
+
+#[test]
+#[should_panic(expected = "value present")]
+fn unwrap_or_panic_with_none() {
+    None::<u32>.unwrap_or_panic_with("value present");
+}
+
+#[test]
+#[should_panic(expected = "lookup succeeds: InnerErr(3)")]
+fn unwrap_or_panic_with_err() {
+    Err::<u32, _>(InnerErr(3)).unwrap_or_panic_with("lookup succeeds");
+}
+
+#[test]
+fn into_some_flatten() {
+    assert_eq!(Some(42), Some(Some(42)).into_some_flatten());
+    assert_eq!(None, Some(None::<u32>).into_some_flatten());
+    assert_eq!(None, None::<Option<u32>>.into_some_flatten());
+    let o = Some("Foobar");
+    assert_eq!(Some("Foo"), o.and_then(|s| s.get(0..3)).into_some().into_some_flatten());
+}
+
+#[track_caller]
+fn caller_location() -> &'static ::std::panic::Location<'static> {
+    ::std::panic::Location::caller()
+}
+
+#[test]
+#[allow(clippy::redundant_closure_call)]
+fn fn_expr_keeps_caller_location() {
+    let (loc, line) = (fn_expr!{ caller_location() }, line!());
+    assert_eq!(line, loc.line());
+    assert_eq!(file!(), loc.file());
+}
+
+#[test]
+#[allow(clippy::redundant_closure_call)]
+fn fn_expr_panic_location() {
+    use std::panic::{catch_unwind, set_hook, take_hook, Location};
+    use std::sync::Mutex;
+    static PANIC_LINE: Mutex<Option<u32>> = Mutex::new(None);
+    let previous_hook = take_hook();
+    set_hook(Box::new(|info| {
+        if info.payload_as_str() == Some("location test") {
+            *PANIC_LINE.lock().unwrap() = info.location().map(Location::line);
         }
+    }));
+    let o = "x".parse::<u32>().ok();
+    let (res, line) = (catch_unwind(|| fn_expr!{ o.expect("location test") }), line!());
+    set_hook(previous_hook);
+    assert!(res.is_err());
+    assert_eq!(Some(line), *PANIC_LINE.lock().unwrap());
+}
+
+#[test]
+fn into_ok_err_infallible() {
+    use std::convert::Infallible;
+    let res: Result<Infallible, InnerErr> = InnerErr(3).into_ok_err();
+    let code = match res {
+        Err(InnerErr(code)) => code,
     };
-    assert_eq!(u32::max_value(), i);
+    assert_eq!(3, code);
+    let widened: Result<u32, InnerErr> = InnerErr(4).into_ok_err().map(|never| match never {});
+    assert_eq!(Err(InnerErr(4)), widened);
+}
+
+#[test]
+fn option_filter_not() {
+    assert_eq!(Some(3), Some(3).filter_not(|&n| n == 0));
+    assert_eq!(None, Some(0).filter_not(|&n| n == 0));
+    assert_eq!(None, None::<u32>.filter_not(|_| unreachable!()));
+}
+
+#[test]
+fn result_err_filter() {
+    let kept: Result<u32, InnerErr> = Ok(42);
+    assert_eq!(Ok(42), kept.err_filter(|&n| n > 0, InnerErr));
+    let rejected: Result<u32, InnerErr> = Ok(0);
+    assert_eq!(Err(InnerErr(0)), rejected.err_filter(|&n| n > 0, InnerErr));
+    let failed: Result<u32, InnerErr> = Err(InnerErr(7));
+    assert_eq!(Err(InnerErr(7)), failed.err_filter(|_| unreachable!(), |_| unreachable!()));
+}
+
+#[test]
+fn fn_expr_builder_borrowing() {
+    let o = Some("Foobar");
+    let s = FnExprBuilder::new().run(|| o?.get(0..3)?.to_lowercase().into_some());
+    assert_eq!(Some("foo".to_string()), s);
+    let res = FnExprBuilder::default()
+        .with_return::<Result<u32, OuterErr>>()
+        .run(|| Err::<u32, _>(InnerErr(1))?.into_ok());
+    assert_eq!(Err(OuterErr("inner 1".to_string())), res);
+}
+
+#[test]
+fn fn_expr_builder_moving() {
+    let o = Some("Foobar".to_string());
+    let s = FnExprBuilder::new()
+        .move_capture()
+        .with_return::<Option<String>>()
+        .run(move || o?.get(0..3)?.to_lowercase().into_some());
+    assert_eq!(Some("foo".to_string()), s);
+}
+
+#[derive(Debug, PartialEq)]
+struct ContextErr {
+    code: u32,
+    context: Vec<String>,
+}
+
+impl From<InnerErr> for ContextErr {
+    fn from(e: InnerErr) -> ContextErr {
+        ContextErr { code: e.0, context: Vec::new() }
+    }
+}
+
+impl AddContext for ContextErr {
+    fn add_context(mut self, context: &str) -> ContextErr {
+        self.context.push(context.to_string());
+        self
+    }
+}
+
+fn load_setting(fail: bool) -> Result<u32, ContextErr> {
+    fn_expr_ctx!{ "loading setting": {
+        if fail {
+            Err(InnerErr(2))?;
+        }
+        Ok(1)
+    }}
+}
+
+#[test]
+fn fn_expr_ctx_attaches_context() {
+    assert_eq!(Ok(1), load_setting(false));
+    let res = fn_expr_ctx!{ "loading config": Result<u32, ContextErr>: {
+        load_setting(true)?.into_ok()
+    }};
+    let err = res.unwrap_err();
+    assert_eq!(2, err.code);
+    assert_eq!(vec!["loading setting".to_string(), "loading config".to_string()], err.context);
+}
+
+#[test]
+fn into_some_then_equivalence() {
+    let half = |i: u32| if i & 1 == 0 { Some(i / 2) } else { None };
+    assert_eq!(42.into_some().and_then(half), 42.into_some_then(half));
+    assert_eq!(Some(21), 42.into_some_then(half));
+    assert_eq!(41.into_some().and_then(half), 41.into_some_then(half));
+    assert_eq!(None, 41.into_some_then(half));
+}
+
+#[test]
+fn into_array_one() {
+    assert_eq!([7], 7.into_array_one());
+    let words: &[String] = &"foo".to_string().into_array_one();
+    assert_eq!(1, words.len());
+    assert_eq!("foo", words[0]);
+}
+
+#[test]
+fn into_ok_then_equivalence() {
+    let validate = |i: u32| if i > 0 { Ok(i) } else { Err(InnerErr(i)) };
+    assert_eq!(42.into_ok().and_then(validate), 42.into_ok_then(validate));
+    assert_eq!(Ok(42), 42.into_ok_then(validate));
+    assert_eq!(0.into_ok().and_then(validate), 0.into_ok_then(validate));
+    assert_eq!(Err(InnerErr(0)), 0.into_ok_then(validate));
+}
+
+fn build_message(i: u32) -> Option<String> {
+    if i > 0 {
+        Some(format!("failed after {} attempts", i))
+    } else {
+        None
+    }
+}
+
+#[test]
+fn into_err_annotated() {
+    let res: Result<u32, String> = "foo".to_string().into_err();
+    assert_eq!(Err("foo".to_string()), res);
+    let attempts = 3;
+    let res = fn_expr!{ Option<Result<u32, String>>: build_message(attempts)?.into_err().into_some() };
+    assert_eq!(Some(Err("failed after 3 attempts".to_string())), res);
+}
+
+#[test]
+fn into_err_inferred() {
+    let mut res = InnerErr(1).into_err();
+    assert_eq!(Err(InnerErr(1)), res);
+    res = Ok(42);
+    assert_eq!(Ok(42), res);
+    let attempts = 0;
+    let res = fn_expr!{ build_message(attempts)?.into_err().into_some() };
+    assert_eq!(None::<Result<(), String>>, res);
+}
+
+#[test]
+fn into_some_zip() {
+    assert_eq!(Some((1, "one")), 1.into_some_zip("one"));
+    assert_eq!(Some((1, "one")), 1.into_some_zip_opt(Some("one")));
+    assert_eq!(None, 1.into_some_zip_opt(None::<&str>));
+    let other = Some("one");
+    assert_eq!(1.into_some().zip(other), 1.into_some_zip_opt(other));
+}
+
+#[test]
+fn option_or_try() {
+    use std::num::ParseIntError;
+    let present: Result<u32, ParseIntError> = Some(42).or_try(|| unreachable!());
+    assert_eq!(Ok(42), present);
+    assert_eq!(Ok(7), None.or_try(|| "7".parse::<u32>()));
+    assert!(None.or_try(|| "seven".parse::<u32>()).is_err());
+}
+
+#[test]
+fn none_if_method() {
+    assert_eq!(None, "".none_if(|s| s.is_empty()));
+    assert_eq!(Some("foo"), "foo".none_if(|s| s.is_empty()));
+    assert_eq!(Some(""), "".some_if(|s| s.is_empty()));
+    assert_eq!(None, "foo".some_if(|s| s.is_empty()));
+}
+
+fn first_word(text: &str) -> Option<&str> {
+    fn_expr!{ text.split_whitespace().next()?.into_some_ref() }
+}
+
+#[test]
+fn into_some_ref() {
+    let text = "foo bar".to_string();
+    let word = first_word(&text);
+    assert_eq!(Some("foo"), word);
+    // the value is only borrowed, so it is still usable afterwards
+    assert_eq!(Some(&text), text.into_some_ref());
+}
+
+#[test]
+#[allow(clippy::question_mark)] // testing `let ... else` syntax on purpose
+#[allow(deprecated)]
+fn let_else_in_block() {
+    let o: Option<&str> = Some("42");
+    let res = fn_expr!{{
+        let Some(s) = o else { return None };
+        let Ok(i) = s.parse::<u32>() else { return None };
+        Some(i)
+    }};
+    assert_eq!(Some(42), res);
+    let o: Option<&str> = Some("foo");
+    let res = fn_block!{ Option<u32>: {
+        let Some(s) = o else { return None };
+        let Ok(i) = s.parse::<u32>() else { return None };
+        Some(i)
+    }};
+    assert_eq!(None, res);
+}
+
+#[test]
+fn ok_or_if() {
+    assert_eq!(Ok(42), 42.ok_or_if(|&i| i > 0, "not positive"));
+    assert_eq!(Err("not positive"), (-42).ok_or_if(|&i| i > 0, "not positive"));
+}
+
+#[test]
+fn ok_or_else_if_lazy() {
+    let mut created = 0;
+    assert_eq!(Ok(42), 42.ok_or_else_if(|&i| i > 0, || { created += 1; "not positive" }));
+    assert_eq!(0, created);
+    assert_eq!(Err("not positive"), (-42).ok_or_else_if(|&i| i > 0, || { created += 1; "not positive" }));
+    assert_eq!(1, created);
+}
+
+#[test]
+fn result_ok_log_err() {
+    let mut logged = Vec::new();
+    assert_eq!(None, "x".parse::<u32>().ok_log_err(|e| logged.push(e.to_string())));
+    assert_eq!(1, logged.len());
+    assert_eq!(Some(42), "42".parse::<u32>().ok_log_err(|_| unreachable!()));
+}
+
+fn first_word_ok(text: &str) -> Result<&str, DefaultErr> {
+    fn_expr!{ text.split_whitespace().next().ok_or(DefaultErr)?.into_ok_ref() }
+}
+
+#[test]
+fn into_ok_ref() {
+    let text = "foo bar".to_string();
+    let word = first_word_ok(&text);
+    assert_eq!(Ok("foo"), word);
+    assert!(first_word_ok("").is_err());
+    // the value is only borrowed, so it is still usable afterwards
+    assert_eq!(Ok::<_, DefaultErr>(&text), text.into_ok_ref());
+}
+
+#[test]
+fn tap_order() {
+    let mut events = Vec::new();
+    let o = Some("Foobar");
+    let res = fn_expr!{
+        o?.tap(|s| events.push(format!("input: {}", s)))
+            .get(0..3)?
+            .tap(|s| events.push(format!("slice: {}", s)))
+            .to_lowercase()
+            .into_some()
+    };
+    assert_eq!("foo", res.unwrap());
+    assert_eq!(vec!["input: Foobar", "slice: Foo"], events);
+}
+
+#[test]
+fn into_some_mut_and_into_ok_mut() {
+    let mut counts = vec![1, 2, 3];
+    let counts_ref = &mut counts;
+    if let Some(first) = fn_expr!{ { counts_ref }.first_mut()?.into_some_mut() } {
+        *first += 10;
+    }
+    let counts_ref = &mut counts;
+    let last: Result<&mut i32, DefaultErr> = fn_expr!{ { counts_ref }.last_mut().ok_or(DefaultErr)?.into_ok_mut() };
+    if let Ok(last) = last {
+        *last += 10;
+    }
+    assert_eq!(vec![11, 2, 13], counts);
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct MissingErr;
+
+impl From<::std::num::ParseIntError> for MissingErr {
+    fn from(_: ::std::num::ParseIntError) -> MissingErr {
+        MissingErr
+    }
+}
+
+#[test]
+fn fn_expr_res_mixed() {
+    let o = Some("Foobar 42");
+    let res = fn_expr_res!{ MissingErr: o?.get(7..)?.parse::<u32>()? };
+    assert_eq!(Ok(42), res);
+    let o = Some("Foo");
+    let res = fn_expr_res!{ MissingErr: o?.get(7..)?.parse::<u32>()? };
+    assert_eq!(Err(MissingErr), res);
+    let o: Option<&str> = None;
+    let res = fn_expr_res!{ MissingErr: {
+        let s = o?;
+        s.len()
+    }};
+    assert_eq!(Err(MissingErr), res);
+}
+
+#[test]
+#[allow(clippy::redundant_closure_call)]
+fn tap_mut_visible_downstream() {
+    let v = vec![3, 1, 2];
+    let first = fn_expr!{ v.tap_mut(|v| v.sort()).tap_mut(|v| v.retain(|&i| i > 1)).into_iter().next() };
+    assert_eq!(Some(2), first);
+}
+
+#[test]
+fn result_flatten_ok() {
+    let ok: Result<Result<u32, &str>, &str> = Ok(Ok(1));
+    assert_eq!(Ok(1), ok.flatten_ok());
+    let inner_err: Result<Result<u32, &str>, &str> = Ok(Err("inner"));
+    assert_eq!(Err("inner"), inner_err.flatten_ok());
+    let outer_err: Result<Result<u32, &str>, &str> = Err("outer");
+    assert_eq!(Err("outer"), outer_err.flatten_ok());
+}
+
+#[test]
+fn fn_expr_ref_keeps_captures() {
+    let name = Some("Foobar".to_string());
+    let prefix = fn_expr_ref!{ name.as_ref()?.get(0..3)?.to_lowercase().into_some() };
+    assert_eq!("foo", prefix.unwrap());
+    let len = fn_expr_ref!{ Option<usize>: {
+        let n = name.as_ref()?;
+        n.len().into_some()
+    }};
+    assert_eq!(Some(6), len);
+    assert_eq!(Some("Foobar".to_string()), name);
+}
+
+#[derive(Debug, PartialEq)]
+struct Wrapped<T>(T);
+
+#[test]
+fn wrap_with() {
+    assert_eq!(Wrapped(42), 42.wrap_with(Wrapped));
+    assert_eq!(Some(42), 42.wrap_with(Some));
+    let o = Some("42");
+    let res = fn_expr!{ o?.parse::<u32>().ok()?.wrap_with(Wrapped).wrap_with(Some) };
+    assert_eq!(Some(Wrapped(42)), res);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_box() {
+    use std::fmt::Display;
+    let b: Box<u32> = 42.into_box();
+    assert_eq!(42, *b);
+    let d: Box<dyn Display> = 42.into_box();
+    assert_eq!("42", d.to_string());
+    let o = Some("Foo");
+    let res: Option<Box<String>> = fn_expr!{ o?.to_lowercase().into_box().into_some() };
+    assert_eq!(Some(Box::new("foo".to_string())), res);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_rc() {
+    use std::rc::Rc;
+    let rc = "foo".to_string().into_rc();
+    let other = Rc::clone(&rc);
+    assert_eq!(2, Rc::strong_count(&rc));
+    drop(other);
+    assert_eq!(1, Rc::strong_count(&rc));
+    let o = Some("Foo");
+    let res: Option<Rc<String>> = fn_expr!{ o?.to_lowercase().into_rc().into_some() };
+    assert_eq!(Some(Rc::new("foo".to_string())), res);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_arc() {
+    use std::sync::Arc;
+    use std::thread;
+    let o = Some("Foo");
+    let arc = fn_expr!{ o?.to_lowercase().into_arc().into_some() }.expect("value");
+    let shared = Arc::clone(&arc);
+    assert_eq!(2, Arc::strong_count(&arc));
+    let len = thread::spawn(move || shared.len()).join().expect("thread result");
+    assert_eq!(3, len);
+    assert_eq!(1, Arc::strong_count(&arc));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_mutex() {
+    let mutex = vec![1, 2].into_mutex();
+    mutex.lock().expect("lock").push(3);
+    assert_eq!(vec![1, 2, 3], *mutex.lock().expect("lock"));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_mutex_into_arc_across_thread() {
+    use std::sync::Arc;
+    use std::thread;
+    let counter = 0u32.into_mutex().into_arc();
+    let shared = Arc::clone(&counter);
+    thread::spawn(move || *shared.lock().expect("lock") += 42)
+        .join()
+        .expect("thread result");
+    assert_eq!(42, *counter.lock().expect("lock"));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_rw_lock() {
+    let lock = vec![1, 2].into_rw_lock().into_arc();
+    assert_eq!(2, lock.read().expect("read lock").len());
+    lock.write().expect("write lock").push(3);
+    assert_eq!(vec![1, 2, 3], *lock.read().expect("read lock"));
+}
+
+#[test]
+fn into_ref_cell() {
+    let cell = vec![1, 2].into_ref_cell();
+    cell.borrow_mut().push(3);
+    assert_eq!(vec![1, 2, 3], *cell.borrow());
+    let o = Some("Foo");
+    let res = fn_expr!{ o?.to_lowercase().into_ref_cell().into_some() }.expect("value");
+    res.borrow_mut().push_str("bar");
+    assert_eq!("foobar", *res.borrow());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_ref_cell_into_rc() {
+    use std::rc::Rc;
+    let shared = String::new().into_ref_cell().into_rc();
+    let other = Rc::clone(&shared);
+    other.borrow_mut().push_str("foo");
+    assert_eq!("foo", *shared.borrow());
+}
+
+#[test]
+fn into_cell() {
+    let cell = 41.into_cell();
+    cell.set(cell.get() + 1);
+    assert_eq!(42, cell.get());
+    let names = vec!["foo"].into_cell();
+    assert_eq!(vec!["foo"], names.take());
+    assert!(names.take().is_empty());
+}
+
+#[test]
+fn showcase() {
+    // Not an actual test, but a showcase for several alternative ways to express the same
+    let o = Some("Foobar");
+    let s = o.and_then(|st| st.get(0..3)).map(|st| st.to_lowercase());
+    assert_eq!("foo", s.expect("result is Some"));
+
+    let s = (|| Some(o?.get(0..3)?.to_lowercase()))();
+    assert_eq!("foo", s.expect("result is Some"));
+
+    use super::IntoSome;
+    let s = fn_expr!{ o?.get(0..3)?.to_lowercase().into_some() };
+    assert_eq!("foo", s.expect("result is Some"));
+}
+
+#[cfg(feature = "unproven")]
+use std::num::ParseIntError;
+#[cfg(feature = "unproven")]
+use std::str::Utf8Error;
+
+#[cfg(feature = "unproven")]
+enum ConvertErr {
+    StrParseErr,
+    IntParseErr,
+}
+
+#[cfg(feature = "unproven")]
+impl From<Utf8Error> for ConvertErr {
+    fn from(_: Utf8Error) -> ConvertErr {
+        ConvertErr::StrParseErr
+    }
+}
+#[cfg(feature = "unproven")]
+impl From<ParseIntError> for ConvertErr {
+    fn from(_: ParseIntError) -> ConvertErr {
+        ConvertErr::IntParseErr
+    }
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_result() {
+    use std::str::from_utf8;
+
+    let s: &[u8] = &[0x0020, 0x0034, 0x0032];
+    let i = fn_try! {
+        from_utf8(s)?.trim().parse::<u32>()?
+        => catch {
+            ConvertErr::StrParseErr => 0u32,
+            ConvertErr::IntParseErr => u32::MAX
+        }
+    };
+    assert_eq!(42, i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_error() {
+    use std::str::from_utf8;
+
+    let s: &[u8] = &[0x0020, 0x005A, 0x0032];
+    let i = fn_try! {
+        from_utf8(s)?.trim().parse::<u32>()?
+        => catch {
+            ConvertErr::StrParseErr => 0u32,
+            ConvertErr::IntParseErr => u32::MAX
+        }
+    };
+    assert_eq!(u32::MAX, i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_block_result() {
+    use std::str::from_utf8;
+
+    let s: &[u8] = &[0x0020, 0x0034, 0x0032];
+    let i = fn_try! {{
+        let s = from_utf8(s)?;
+        let s = s.trim();
+        s.parse::<u32>()?
+    } => catch {
+        ConvertErr::StrParseErr => 0u32,
+        ConvertErr::IntParseErr => u32::MAX
+    }};
+    assert_eq!(42, i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_block_error() {
+    use std::str::from_utf8;
+
+    let s: &[u8] = &[0x0020, 0x005A, 0x0032];
+    let mut steps = 0;
+    let i = fn_try! {{
+        let s = from_utf8(s)?;
+        steps += 1;
+        let i = s.trim().parse::<u32>()?;
+        steps += 1;
+        i
+    } => catch {
+        ConvertErr::StrParseErr => 0u32,
+        ConvertErr::IntParseErr => u32::MAX
+    }};
+    assert_eq!(u32::MAX, i);
+    assert_eq!(1, steps);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_block_str_error() {
+    let s: Vec<u8> = vec![0xff, 0x34];
+    let i = fn_try! {{
+        let s = std::str::from_utf8(&s)?;
+        s.parse::<u32>()?
+    } => catch {
+        ConvertErr::StrParseErr => 0u32,
+        ConvertErr::IntParseErr => u32::MAX
+    }};
+    assert_eq!(0, i);
+}
+
+#[cfg(feature = "unproven")]
+enum LookupErr {
+    Missing,
+    NotANumber,
+}
+
+#[cfg(feature = "unproven")]
+impl From<ParseIntError> for LookupErr {
+    fn from(_: ParseIntError) -> LookupErr {
+        LookupErr::NotANumber
+    }
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_missing_to_result() {
+    let o = Some(vec!["17", "4711"]);
+    let i = fn_try! {
+        missing_to(LookupErr::Missing): o.as_ref()?.get(1)?.parse::<u32>()?
+        => catch {
+            LookupErr::Missing => 0u32,
+            LookupErr::NotANumber => u32::MAX
+        }
+    };
+    assert_eq!(4711, i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_missing_to_none() {
+    let o = Some(vec!["17", "4711"]);
+    let i = fn_try! {
+        missing_to(LookupErr::Missing): o.as_ref()?.get(2)?.parse::<u32>()?
+        => catch {
+            LookupErr::Missing => 0u32,
+            LookupErr::NotANumber => u32::MAX
+        }
+    };
+    assert_eq!(0, i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_missing_to_error() {
+    let o = Some(vec!["17", "x"]);
+    let i = fn_try! {
+        missing_to(LookupErr::Missing): {
+            let entries = o.as_ref()?;
+            let first = entries.first()?.parse::<u32>()?;
+            first + entries.get(1)?.parse::<u32>()?
+        }
+        => catch {
+            LookupErr::Missing => 0u32,
+            LookupErr::NotANumber => u32::MAX
+        }
+    };
+    assert_eq!(u32::MAX, i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_typed_result() {
+    use std::str::from_utf8;
+
+    let s: &[u8] = &[0x0020, 0x0034, 0x0032];
+    let i = fn_try! {
+        from_utf8(s)?.trim().parse::<u32>()?
+        => catch Utf8Error as _e { 0u32 }
+           catch ParseIntError as _e { u32::MAX }
+    };
+    assert_eq!(42, i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_typed_error() {
+    use std::str::from_utf8;
+
+    let s: &[u8] = &[0x0020, 0x005A, 0x0032];
+    let i = fn_try! {
+        from_utf8(s)?.trim().parse::<u32>()?
+        => catch Utf8Error as _e { 0u32 }
+           catch ParseIntError as _e { u32::MAX }
+    };
+    assert_eq!(u32::MAX, i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_typed_binding() {
+    use std::str::from_utf8;
+
+    let s: Vec<u8> = vec![0x0020, 0x00C3, 0x0032];
+    let msg = fn_try! {
+        from_utf8(&s)?.trim().parse::<u32>()?.to_string()
+        => catch Utf8Error as e { format!("utf8 error at {}", e.valid_up_to()) }
+           catch ParseIntError as e { e.to_string() }
+    };
+    assert_eq!("utf8 error at 1", msg);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_display_result() {
+    use std::str::from_utf8;
+
+    let s: &[u8] = &[0x0020, 0x0034, 0x0032];
+    let msg = fn_try! {
+        from_utf8(s)?.trim().parse::<u32>()?.to_string()
+        => catch display |msg| format!("failed: {msg}")
+    };
+    assert_eq!("42", msg);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_display_error() {
+    use std::str::from_utf8;
+
+    let s: &[u8] = &[0x0020, 0x005A, 0x0032];
+    let expected = "Z2".parse::<u32>().expect_err("invalid digit").to_string();
+    let msg = fn_try! {
+        from_utf8(s)?.trim().parse::<u32>()?.to_string()
+        => catch display |msg| format!("failed: {msg}")
+    };
+    assert!(msg.starts_with("failed: "));
+    assert!(msg.contains(&expected));
+}
+
+#[cfg(all(feature = "unproven", feature = "tracing"))]
+mod tracing_support {
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Event recorded by the `CollectingSubscriber`: the name of the span the
+    /// event was recorded in (if any) and the formatted fields of the event.
+    pub type RecordedEvent = (Option<&'static str>, String);
+
+    /// Subscriber recording the names of created spans and the
+    /// span (if any) each event was recorded in.
+    #[derive(Clone, Default)]
+    pub struct CollectingSubscriber {
+        pub spans: Arc<Mutex<Vec<&'static str>>>,
+        pub events: Arc<Mutex<Vec<RecordedEvent>>>,
+        current: Arc<Mutex<Vec<u64>>>,
+    }
+
+    #[derive(Debug)]
+    pub struct ParseFailure(#[allow(dead_code)] pub ::std::num::ParseIntError);
+
+    impl From<::std::num::ParseIntError> for ParseFailure {
+        fn from(e: ::std::num::ParseIntError) -> ParseFailure {
+            ParseFailure(e)
+        }
+    }
+
+    struct MessageVisitor<'a>(&'a mut String);
+
+    impl<'a> tracing::field::Visit for MessageVisitor<'a> {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.push_str(&format!("{}={:?};", field.name(), value));
+        }
+    }
+
+    impl Subscriber for CollectingSubscriber {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+        fn new_span(&self, span: &Attributes) -> Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(span.metadata().name());
+            Id::from_u64(spans.len() as u64)
+        }
+        fn record(&self, _: &Id, _: &Record) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event) {
+            let mut fields = String::new();
+            event.record(&mut MessageVisitor(&mut fields));
+            let span = self.current.lock().unwrap().last().map(|id| {
+                self.spans.lock().unwrap()[*id as usize - 1]
+            });
+            self.events.lock().unwrap().push((span, fields));
+        }
+        fn enter(&self, span: &Id) {
+            self.current.lock().unwrap().push(span.into_u64());
+        }
+        fn exit(&self, _: &Id) {
+            self.current.lock().unwrap().pop();
+        }
+    }
+}
+
+#[test]
+#[cfg(all(feature = "unproven", feature = "tracing"))]
+fn fn_catch_span_error() {
+    let subscriber = tracing_support::CollectingSubscriber::default();
+    let i = tracing::subscriber::with_default(subscriber.clone(), || {
+        fn_try! {
+            span "parse_number": "x42".parse::<u32>()?
+            => catch {
+                tracing_support::ParseFailure(_) => 0u32
+            }
+        }
+    });
+    assert_eq!(0, i);
+    assert_eq!(vec!["parse_number"], *subscriber.spans.lock().unwrap());
+    let events = subscriber.events.lock().unwrap();
+    assert_eq!(1, events.len());
+    assert_eq!(Some("parse_number"), events[0].0);
+    assert!(events[0].1.contains("InvalidDigit"));
+}
+
+#[test]
+#[cfg(all(feature = "unproven", feature = "tracing"))]
+fn fn_catch_span_result() {
+    let subscriber = tracing_support::CollectingSubscriber::default();
+    let i = tracing::subscriber::with_default(subscriber.clone(), || {
+        fn_try! {
+            span "parse_number": "42".parse::<u32>()?
+            => catch {
+                tracing_support::ParseFailure(_) => 0u32
+            }
+        }
+    });
+    assert_eq!(42, i);
+    assert_eq!(vec!["parse_number"], *subscriber.spans.lock().unwrap());
+    assert!(subscriber.events.lock().unwrap().is_empty());
+}
+
+#[cfg(feature = "unproven")]
+fn parse_counting(input: &str, counter: &::std::sync::atomic::AtomicUsize) -> u32 {
+    use std::sync::atomic::Ordering;
+    fn_try! {
+        input.parse::<u32>()?
+        => catch {
+            ConvertErr::StrParseErr => 0u32,
+            ConvertErr::IntParseErr => u32::MAX
+        } on_error |_e| counter.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_on_error_success() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let counter = AtomicUsize::new(0);
+    assert_eq!(42, parse_counting("42", &counter));
+    assert_eq!(0, counter.load(Ordering::SeqCst));
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_on_error_failure() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let counter = AtomicUsize::new(0);
+    assert_eq!(u32::MAX, parse_counting("x", &counter));
+    assert_eq!(1, counter.load(Ordering::SeqCst));
+    assert_eq!(7, parse_counting("7", &counter));
+    assert_eq!(u32::MAX, parse_counting("y", &counter));
+    assert_eq!(2, counter.load(Ordering::SeqCst));
+}
+
+#[cfg(all(feature = "unproven", feature = "nightly-try-trait"))]
+mod try_trait_support {
+    use std::convert::Infallible;
+    use std::ops::{ControlFlow, FromResidual, Residual, Try};
+
+    /// Minimal `Try` type collecting validation messages.
+    pub enum Validated<T> {
+        Valid(T),
+        Invalid(Vec<String>),
+    }
+
+    pub struct Invalid(pub Vec<String>);
+
+    impl<T> Try for Validated<T> {
+        type Output = T;
+        type Residual = Invalid;
+
+        fn from_output(output: T) -> Self {
+            Validated::Valid(output)
+        }
+
+        fn branch(self) -> ControlFlow<Invalid, T> {
+            match self {
+                Validated::Valid(v) => ControlFlow::Continue(v),
+                Validated::Invalid(msgs) => ControlFlow::Break(Invalid(msgs)),
+            }
+        }
+    }
+
+    impl<T> Residual<T> for Invalid {
+        type TryType = Validated<T>;
+    }
+
+    impl<T> FromResidual<Invalid> for Validated<T> {
+        fn from_residual(residual: Invalid) -> Self {
+            Validated::Invalid(residual.0)
+        }
+    }
+
+    impl<T> FromResidual<Result<Infallible, String>> for Validated<T> {
+        fn from_residual(residual: Result<Infallible, String>) -> Self {
+            match residual {
+                Err(msg) => Validated::Invalid(vec![msg]),
+                Ok(never) => match never {},
+            }
+        }
+    }
+
+    pub fn positive(i: i32) -> Validated<i32> {
+        if i > 0 {
+            Validated::Valid(i)
+        } else {
+            Validated::Invalid(vec![format!("{} is not positive", i)])
+        }
+    }
+}
+
+#[test]
+#[cfg(all(feature = "unproven", feature = "nightly-try-trait"))]
+fn fn_catch_try_trait_output() {
+    use self::try_trait_support::*;
+
+    let i = fn_try! {
+        as Validated<i32>: positive(20)? + positive(22)?
+        => catch {
+            Invalid(msgs) => -(msgs.len() as i32)
+        }
+    };
+    assert_eq!(42, i);
+}
+
+#[test]
+#[cfg(all(feature = "unproven", feature = "nightly-try-trait"))]
+fn fn_catch_try_trait_residual() {
+    use self::try_trait_support::*;
+
+    let msg = fn_try! {
+        as Validated<String>: {
+            let checked = positive(-1)?;
+            Err::<i32, String>("not reached".to_string())?;
+            checked.to_string()
+        }
+        => catch {
+            Invalid(msgs) => msgs.join(", ")
+        }
+    };
+    assert_eq!("-1 is not positive", msg);
+}
+
+#[test]
+#[cfg(all(feature = "unproven", feature = "nightly-try-trait"))]
+fn fn_catch_try_trait_foreign_residual() {
+    use self::try_trait_support::*;
+
+    let msg = fn_try! {
+        as Validated<String>: (positive(1)? + Err::<i32, String>("failed".to_string())?).to_string()
+        => catch {
+            Invalid(msgs) => msgs.join(", ")
+        }
+    };
+    assert_eq!("failed", msg);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_opt_some() {
+    use std::str::from_utf8;
+
+    let s: &[u8] = &[0x0020, 0x0034, 0x0032];
+    let i = fn_try_opt!{ from_utf8(s)?.trim().parse::<u32>()? };
+    assert_eq!(Some(42), i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_opt_none() {
+    use std::str::from_utf8;
+
+    let s: &[u8] = &[0x0020, 0x005A, 0x0032];
+    let i = fn_try_opt!{ from_utf8(s)?.trim().parse::<u32>()? };
+    assert_eq!(None, i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_opt_pinned_error() {
+    fn parse(s: &str) -> Result<u32, ConvertErr> {
+        Ok(s.trim().parse::<u32>()?)
+    }
+    assert_eq!(Some(42), fn_try_opt!{ as ConvertErr: parse(" 42")? + "0".parse::<u32>()? });
+    assert_eq!(None, fn_try_opt!{ as ConvertErr: parse("x")? });
+}
+
+#[cfg(feature = "log")]
+mod log_support {
+    use log::{Log, Metadata, Record};
+    #[cfg(not(feature = "std"))]
+    use std::{thread_local, prelude::v1::{String, ToString, Vec}};
+    use std::cell::RefCell;
+    use std::sync::Once;
+
+    thread_local! {
+        static RECORDS: RefCell<Vec<(log::Level, String)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Logger recording all log messages of the current thread,
+    /// so tests running in parallel do not see each others messages.
+    struct ThreadLocalLogger;
+
+    impl Log for ThreadLocalLogger {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+        fn log(&self, record: &Record) {
+            RECORDS.with(|r| r.borrow_mut().push((record.level(), record.args().to_string())));
+        }
+        fn flush(&self) {}
+    }
+
+    static LOGGER: ThreadLocalLogger = ThreadLocalLogger;
+    static INIT: Once = Once::new();
+
+    /// Installs the recording logger (once) and returns the messages
+    /// recorded on the current thread while executing `f`.
+    pub fn capture<F: FnOnce()>(f: F) -> Vec<(log::Level, String)> {
+        INIT.call_once(|| {
+            log::set_logger(&LOGGER).expect("no other logger installed");
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+        RECORDS.with(|r| r.borrow_mut().clear());
+        f();
+        RECORDS.with(|r| r.borrow_mut().drain(..).collect())
+    }
+}
+
+#[test]
+#[cfg(all(feature = "unproven", feature = "log"))]
+fn fn_try_opt_log() {
+    let mut i = Some(0);
+    let records = log_support::capture(|| i = fn_try_opt!{ log: "x".parse::<u32>()? });
+    assert_eq!(None, i);
+    assert_eq!(1, records.len());
+    assert_eq!(log::Level::Warn, records[0].0);
+    assert!(records[0].1.contains("InvalidDigit"));
+
+    let records = log_support::capture(|| i = fn_try_opt!{ log: "42".parse::<u32>()? });
+    assert_eq!(Some(42), i);
+    assert!(records.is_empty());
+}
+
+#[test]
+#[cfg(all(feature = "unproven", feature = "log"))]
+fn fn_try_opt_log_pinned_error() {
+    let mut i = Some(0);
+    let records = log_support::capture(|| i = fn_try_opt!{ log as InnerErr: Err(InnerErr(7))? });
+    assert_eq!(None::<u32>, i);
+    assert_eq!(1, records.len());
+    assert!(records[0].1.contains("InnerErr(7)"));
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_expect_success() {
+    let input = "42";
+    let i = fn_try_expect!{ "parsing {input}": input.parse::<u32>()? };
+    assert_eq!(42, i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+#[should_panic(expected = "parsing x42 as number: ParseIntError { kind: InvalidDigit }")]
+fn fn_try_expect_failure() {
+    let input = "x42";
+    let _i = fn_try_expect!{ "parsing {input} as number": input.parse::<u32>()? };
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+#[should_panic(expected = "step 2 of 3: InnerErr(7)")]
+fn fn_try_expect_failure_custom_error() {
+    let step = 2;
+    let _i: u32 = fn_try_expect!{ "step {step} of 3": Err(InnerErr(7))? };
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_logged_result() {
+    let (i, err) = fn_try_logged! {
+        "42".parse::<u32>()?
+        => catch {
+            LookupErr::Missing => 0u32,
+            LookupErr::NotANumber => u32::MAX
+        }
+    };
+    assert_eq!(42, i);
+    assert!(err.is_none());
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_logged_error() {
+    let (i, err) = fn_try_logged! {
+        Err(InnerErr(7))?
+        => catch {
+            InnerErr(code) => *code + 1
+        }
+    };
+    assert_eq!(8, i);
+    assert_eq!(Some(InnerErr(7)), err);
+}
+
+#[test]
+#[cfg(all(feature = "unproven", feature = "std"))]
+fn fn_try_timeout_expired() {
+    use std::thread::sleep;
+    use std::time::Duration;
+    let i = fn_try! {
+        timeout(Duration::from_millis(10)):
+        {
+            sleep(Duration::from_millis(500));
+            Err(InnerErr(1))?;
+            1u32
+        }
+        => catch {
+            timeout => 0u32,
+            InnerErr(code) => code + 100
+        }
+    };
+    assert_eq!(0, i);
+}
+
+#[test]
+#[cfg(all(feature = "unproven", feature = "std"))]
+fn fn_try_timeout_in_time() {
+    use std::time::Duration;
+    let input = String::from("42");
+    let i = fn_try! {
+        timeout(Duration::from_secs(10)):
+        input.parse::<u32>()?
+        => catch {
+            timeout => 0u32,
+            LookupErr::Missing => 1,
+            LookupErr::NotANumber => 2
+        }
+    };
+    assert_eq!(42, i);
+    let e = fn_try! {
+        timeout(Duration::from_secs(10)):
+        {
+            Err(InnerErr(7))?;
+            0u32
+        }
+        => catch {
+            timeout => 0u32,
+            InnerErr(code) => code + 100
+        }
+    };
+    assert_eq!(107, e);
+}
+
+#[cfg(feature = "unproven")]
+fn recover_lookup(e: LookupErr) -> u32 {
+    match e {
+        LookupErr::Missing => 0,
+        LookupErr::NotANumber => u32::MAX,
+    }
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_handler_fn() {
+    let i = fn_try! { "x".parse::<u32>()? => catch recover_lookup };
+    assert_eq!(u32::MAX, i);
+    let i = fn_try! { "42".parse::<u32>()? => catch recover_lookup };
+    assert_eq!(42, i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_handler_closure() {
+    let fallback = 7;
+    let i = fn_try! { "x".parse::<u32>()? => catch |_: LookupErr| fallback };
+    assert_eq!(7, i);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_handler_method() {
+    struct Recovery {
+        fallback: String,
+    }
+    impl Recovery {
+        fn recover(&self, e: InnerErr) -> String {
+            format!("{}{}", self.fallback, e.0)
+        }
+    }
+    let recovery = Recovery { fallback: "failed".to_string() };
+    let s = fn_try! {
+        {
+            Err(InnerErr(3))?;
+            "ok".to_string()
+        }
+        => catch |e| recovery.recover(e)
+    };
+    assert_eq!("failed3", s);
+}
+
+#[cfg(feature = "unproven")]
+fn parse_layered(primary: &str, secondary: &str) -> u32 {
+    fn_try! {
+        primary.parse::<u32>()?
+        => catch {
+            LookupErr::NotANumber => fn_try! {
+                secondary.parse::<u32>()? * 10
+                => catch {
+                    LookupErr::NotANumber => 0,
+                    LookupErr::Missing => 1
+                }
+            },
+            LookupErr::Missing => 1
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_nested_fn_try() {
+    assert_eq!(4, parse_layered("4", "2"));
+    assert_eq!(20, parse_layered("x", "2"));
+    assert_eq!(0, parse_layered("x", "y"));
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_nested_fn_try_binding() {
+    // the inner expression uses the binding of the outer pattern
+    let i = fn_try! {
+        {
+            Err(InnerErr(4))?;
+            0u32
+        }
+        => catch {
+            InnerErr(code) => fn_try! {
+                {
+                    if code > 3 {
+                        Err(InnerErr(code * 2))?;
+                    }
+                    code
+                }
+                => catch {
+                    InnerErr(e) => e + 1
+                }
+            }
+        }
+    };
+    assert_eq!(9, i);
+}
+
+#[cfg(feature = "unproven")]
+#[derive(Debug, PartialEq)]
+enum FieldErr {
+    Host,
+    Port,
+}
+
+#[cfg(feature = "unproven")]
+fn flagged<T>(flag: &::std::cell::Cell<bool>, res: Result<T, FieldErr>) -> Result<T, FieldErr> {
+    flag.set(true);
+    res
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_all_success() {
+    let pair = fn_try_all! {
+        [Ok::<_, FieldErr>("localhost"), "8080".parse::<u16>().map_err(|_| FieldErr::Port)]
+        => catch {
+            FieldErr::Host => ("", 0),
+            FieldErr::Port => ("localhost", 80)
+        }
+    };
+    assert_eq!(("localhost", 8080), pair);
+    let five = fn_try_all! {
+        [
+            "1".parse::<u32>(),
+            "2".parse::<u8>(),
+            "3".parse::<u64>(),
+            "4".parse::<i16>(),
+            "5".parse::<usize>(),
+        ]
+        => catch {
+            LookupErr::Missing => (0, 0, 0, 0, 0),
+            LookupErr::NotANumber => (0, 0, 0, 0, 0)
+        }
+    };
+    assert_eq!((1, 2, 3, 4, 5), five);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_all_short_circuits() {
+    use std::cell::Cell;
+    let (first, second, third) = (Cell::new(false), Cell::new(false), Cell::new(false));
+    let caught = Cell::new(None);
+    let triple = fn_try_all! {
+        [
+            flagged(&first, Ok(1)),
+            flagged(&second, Err(FieldErr::Host)),
+            flagged(&third, Ok(3)),
+        ]
+        => catch {
+            e => {
+                caught.set(Some(e));
+                (0, 0, 0)
+            }
+        }
+    };
+    assert_eq!((0, 0, 0), triple);
+    assert_eq!(Some(FieldErr::Host), caught.into_inner());
+    assert!(first.get());
+    assert!(second.get());
+    assert!(!third.get());
+}
+
+#[test]
+#[cfg(feature = "log")]
+fn unwrap_or_log_option() {
+    let mut name = None;
+    let records = log_support::capture(|| name = Some(None::<String>.unwrap_or_log("no name")));
+    assert_eq!(Some(String::new()), name);
+    assert_eq!(vec![(log::Level::Warn, "no name".to_string())], records);
+
+    let records = log_support::capture(|| name = Some(Some("foo".to_string()).unwrap_or_log("no name")));
+    assert_eq!(Some("foo".to_string()), name);
+    assert!(records.is_empty());
+}
+
+#[test]
+#[cfg(feature = "log")]
+fn unwrap_or_log_result() {
+    let mut i = 7;
+    let records = log_support::capture(|| i = Err::<u32, _>(InnerErr(3)).unwrap_or_log("lookup failed"));
+    assert_eq!(0, i);
+    assert_eq!(vec![(log::Level::Warn, "lookup failed: InnerErr(3)".to_string())], records);
+
+    let records = log_support::capture(|| i = Ok::<u32, InnerErr>(42).unwrap_or_log("lookup failed"));
+    assert_eq!(42, i);
+    assert!(records.is_empty());
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_inline_loop_control() {
+    let inputs = ["1", "skip", "2", "stop", "3"];
+    let mut sum = 0u32;
+    let mut pos = 0;
+    let stopped_at = loop {
+        if pos == inputs.len() {
+            break None;
+        }
+        let input = inputs[pos];
+        pos += 1;
+        sum += fn_try! {
+            inline input.parse::<u32>().map_err(|_| input)
+            => catch {
+                "skip" => continue,
+                other => break Some(other)
+            }
+        };
+    };
+    assert_eq!(Some("stop"), stopped_at);
+    assert_eq!(3, sum);
+}
+
+#[cfg(feature = "unproven")]
+fn parse_or_return(input: &str) -> Option<u32> {
+    let i = fn_try! {
+        inline input.parse::<u32>().and_then(|i| "2".parse::<u32>().map(|j| i * j))
+        => catch {
+            _e => return if input.is_empty() { Some(0) } else { None }
+        }
+    };
+    Some(i + 1)
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_inline_return() {
+    assert_eq!(Some(43), parse_or_return("21"));
+    assert_eq!(Some(0), parse_or_return(""));
+    assert_eq!(None, parse_or_return("x"));
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_or_default() {
+    assert_eq!(0, fn_try_or!{ "x".parse::<u32>()?, 0 });
+    assert_eq!(42, fn_try_or!{ "42".parse::<u32>()?, 0 });
+    let s: Vec<u8> = vec![0xff];
+    let mut defaults = 0;
+    let parsed = fn_try_or!{ std::str::from_utf8(&s)?.parse::<u32>()?, { defaults += 1; 7 } };
+    assert_eq!(7, parsed);
+    assert_eq!(1, defaults);
+    let parsed = fn_try_or!{ "1".parse::<u32>()?, { defaults += 1; 7 } };
+    assert_eq!(1, parsed);
+    assert_eq!(1, defaults);
+}
+
+#[test]
+#[cfg(all(feature = "unproven", feature = "backtrace"))]
+fn fn_catch_backtrace_captured() {
+    use std::backtrace::BacktraceStatus;
+    ::std::env::set_var("RUST_BACKTRACE", "1");
+    let (i, status) = fn_try! {
+        ("x".parse::<u32>()?, BacktraceStatus::Unsupported)
+        => catch (err, trace) {
+            match err {
+                LookupErr::Missing => (1, trace.status()),
+                LookupErr::NotANumber => (0, trace.status())
+            }
+        }
+    };
+    assert_eq!(0, i);
+    assert_eq!(BacktraceStatus::Captured, status);
+}
+
+#[test]
+#[cfg(all(feature = "unproven", not(feature = "backtrace")))]
+fn fn_catch_backtrace_placeholder() {
+    let (code, trace) = fn_try! {
+        {
+            Err(InnerErr(3))?;
+            (0, CaughtBacktrace)
+        }
+        => catch (InnerErr(code), trace) {
+            (code, trace)
+        }
+    };
+    assert_eq!(3, code);
+    assert_eq!(CaughtBacktrace, trace);
+    assert_eq!("backtrace capturing disabled", trace.to_string());
+}
+
+#[test]
+#[cfg(feature = "either")]
+fn into_left_right() {
+    use either::Either;
+    let l: Either<u32, String> = 42.into_left();
+    assert_eq!(Either::Left(42), l);
+    let r: Either<u32, String> = "foo".to_string().into_right();
+    assert_eq!(Either::Right("foo".to_string()), r);
+    let o = Some("Foobar");
+    let e: Option<Either<&str, usize>> = fn_expr!{
+        if o?.len() > 3 { o?.get(0..3)?.into_left() } else { o?.len().into_right() }.into_some()
+    };
+    assert_eq!(Some(Either::Left("Foo")), e);
+}
+
+#[cfg(feature = "unproven")]
+fn collect_dimensions(inputs: [&str; 3], errs: &mut Vec<OuterErr>) -> (u32, u32, u32) {
+    let width = fn_try! {
+        inputs[0].parse::<u32>().map_err(|_| InnerErr(0))?
+        => collect_errors into errs; else 80
+    };
+    let height = fn_try! {
+        inputs[1].parse::<u32>().map_err(|_| InnerErr(1))?
+        => collect_errors into errs; else 25
+    };
+    let depth = fn_try! {
+        inputs[2].parse::<u32>().map_err(|_| InnerErr(2))?
+        => collect_errors into errs; else 1
+    };
+    (width, height, depth)
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_collect_errors() {
+    let mut errs = Vec::new();
+    assert_eq!((80, 24, 1), collect_dimensions(["x", "24", "y"], &mut errs));
+    assert_eq!(vec![OuterErr("inner 0".to_string()), OuterErr("inner 2".to_string())], errs);
+
+    let mut errs = Vec::new();
+    assert_eq!((1, 2, 3), collect_dimensions(["1", "2", "3"], &mut errs));
+    assert!(errs.is_empty());
+}
+
+#[cfg(feature = "unproven")]
+static LIMIT: u32 = 10;
+
+#[cfg(feature = "unproven")]
+enum PayloadErr {
+    IntParse { input: String, pos: usize },
+    Io(u32, String),
+    Code(u32),
+    Limit(&'static u32),
+    Nested(Option<InnerErr>),
+}
+
+#[cfg(feature = "unproven")]
+fn recover_payload(err: PayloadErr) -> String {
+    fn_try! {
+        Err(err)?
+        => catch {
+            PayloadErr::IntParse { input, pos: 0 } => format!("empty {}", input),
+            PayloadErr::IntParse { input, .. } => format!("parse {}", input),
+            PayloadErr::Io(0, _) => "io unknown".to_string(),
+            PayloadErr::Io(_, msg) => format!("io {}", msg),
+            PayloadErr::Code(code @ 400..=499) => format!("client {}", code),
+            PayloadErr::Code(ref code) => format!("code {}", code),
+            PayloadErr::Limit(&limit) => format!("limit {}", limit),
+            PayloadErr::Nested(Some(InnerErr(inner))) => format!("inner {}", inner),
+            PayloadErr::Nested(None) => "nothing".to_string()
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_struct_variant_patterns() {
+    let err = PayloadErr::IntParse { input: "x".to_string(), pos: 0 };
+    assert_eq!("empty x", recover_payload(err));
+    let err = PayloadErr::IntParse { input: "4x".to_string(), pos: 1 };
+    assert_eq!("parse 4x", recover_payload(err));
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_tuple_variant_patterns() {
+    assert_eq!("io denied", recover_payload(PayloadErr::Io(13, "denied".to_string())));
+    assert_eq!("io unknown", recover_payload(PayloadErr::Io(0, "denied".to_string())));
+    assert_eq!("inner 3", recover_payload(PayloadErr::Nested(Some(InnerErr(3)))));
+    assert_eq!("nothing", recover_payload(PayloadErr::Nested(None)));
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_catch_binding_and_reference_patterns() {
+    assert_eq!("client 404", recover_payload(PayloadErr::Code(404)));
+    assert_eq!("code 500", recover_payload(PayloadErr::Code(500)));
+    assert_eq!("limit 10", recover_payload(PayloadErr::Limit(&LIMIT)));
+}
+
+#[cfg(all(feature = "unproven", feature = "std"))]
+fn main_like(input: &str) -> ::std::process::ExitCode {
+    fn_main_try! {
+        {
+            let code = input.parse::<u32>()?;
+            if code > 0 {
+                Err(LookupErr::Missing)?;
+            }
+        }
+        => catch {
+            LookupErr::NotANumber => 2,
+            LookupErr::Missing => 1
+        }
+    }
+}
+
+#[test]
+#[cfg(all(feature = "unproven", feature = "std"))]
+#[allow(clippy::redundant_closure_call)]
+fn fn_main_try_exit_codes() {
+    use std::process::ExitCode;
+    assert_eq!(ExitCode::SUCCESS, main_like("0"));
+    assert_eq!(ExitCode::from(2), main_like("x"));
+    assert_eq!(ExitCode::from(1), main_like("5"));
+    let explicit = fn_main_try! { "7".parse::<u8>()? => catch { LookupErr::NotANumber => 2u8, LookupErr::Missing => 1 } };
+    assert_eq!(ExitCode::from(7), explicit);
+    let out_of_range = fn_main_try! { 300 => catch { InnerErr(_) => 1 } };
+    assert_eq!(ExitCode::FAILURE, out_of_range);
+}
+
+#[cfg(feature = "unproven")]
+fn parse_keep_result(input: &str) -> Result<u32, OuterErr> {
+    fn_try_keep_result! {
+        input.parse::<u32>()?
+        => catch {
+            LookupErr::NotANumber => 0,
+            LookupErr::Missing => 1
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_keep_result_ok() {
+    use std::convert::Infallible;
+    assert_eq!(Ok(42), parse_keep_result("42"));
+    assert_eq!(Ok(0), parse_keep_result("x"));
+    let res: Result<u32, Infallible> = fn_try_keep_result! { "x".parse::<u32>()? => catch |_: LookupErr| 7 };
+    assert_eq!(Ok(7), res);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_all_aggregate_errors() {
+    let res = fn_try_all!{
+        Err::<u32, _>(InnerErr(1))?, Ok::<u32, InnerErr>(2)?, Err::<u32, _>(InnerErr(3))?
+        => catch {
+            errors: Vec<OuterErr> => {
+                assert_eq!(vec![OuterErr("inner 1".to_string()), OuterErr("inner 3".to_string())], errors);
+                (0, 0, 0)
+            }
+        }
+    };
+    assert_eq!((0, 0, 0), res);
+}
+
+#[test]
+#[cfg(feature = "unproven")]
+fn fn_try_all_aggregate_success() {
+    let res = fn_try_all!{
+        "1".parse::<u32>()?, "2".parse::<u32>()?
+        => catch {
+            _errors: Vec<ParseIntError> => unreachable!()
+        }
+    };
+    assert_eq!((1, 2), res);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_some_cow() {
+    let borrowed: Option<Cow<str>> = Cow::Borrowed("foo").into_some();
+    match borrowed {
+        Some(Cow::Borrowed(s)) => assert_eq!("foo", s),
+        _ => panic!("expected borrowed Cow"),
+    }
+    let owned: Option<Cow<[u8]>> = Cow::<[u8]>::Owned(vec![1, 2]).into_some();
+    match owned {
+        Some(Cow::Owned(v)) => assert_eq!(vec![1, 2], v),
+        _ => panic!("expected owned Cow"),
+    }
+}
+
+#[test]
+fn into_some_non_null() {
+    let mut values = [1, 2, 3];
+    let ptr = unsafe { values.as_mut_ptr().add(1).into_some_non_null() };
+    let non_null = ptr.expect("pointer wrapped into Some");
+    unsafe {
+        *non_null.as_ptr() = 42;
+    }
+    assert_eq!([1, 42, 3], values);
+}
+
+#[test]
+fn option_tap_none() {
+    let mut calls = 0;
+    assert_eq!(Some(1), Some(1).tap_none(|| calls += 1));
+    assert_eq!(0, calls);
+    assert_eq!(None::<u32>, None.tap_none(|| calls += 1));
+    assert_eq!(1, calls);
+}
+
+#[test]
+fn into_some_nonzero() {
+    use std::num::*;
+    macro_rules! check_into_some {
+        ($($nz:ident),+) => {$(
+            let o = Some(7);
+            let res = fn_expr!{ { $nz::new(o?)? }.into_some() };
+            assert_eq!($nz::new(7), res);
+            assert_eq!(Some(Some($nz::new(1).unwrap())), $nz::new(1).into_some());
+            assert_eq!(Some(None), $nz::new(0).into_some());
+        )+};
+    }
+    check_into_some!(NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize);
+    check_into_some!(NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize);
+}
+
+#[test]
+fn nonzero_some() {
+    use fns::nonzero_some;
+    use std::num::{NonZeroU16, NonZeroU32, NonZeroU8};
+    assert_eq!(NonZeroU32::new(8), nonzero_some(NonZeroU8::new(8).unwrap()));
+    assert_eq!(NonZeroU32::new(300), nonzero_some(NonZeroU16::new(300).unwrap()));
+    assert_eq!(None, NonZeroU32::new(0));
+}
+
+#[test]
+#[cfg(all(feature = "unproven", feature = "nightly-coroutines"))]
+fn fn_gen_yields_values() {
+    let start = 40;
+    let mut values = fn_gen!{
+        yield start + 1;
+        yield start + 2;
+    };
+    assert_eq!(Some(41), values.next());
+    assert_eq!(Some(42), values.next());
+    assert_eq!(None, values.next());
+}
+
+#[test]
+fn into_ok_or_into() {
+    #[derive(Debug, PartialEq)]
+    struct MyErr;
+    let res = 42u32.into_ok_or_into::<MyErr>();
+    assert_eq!(Ok(42), res);
+    let o = Some("7");
+    let parsed = fn_expr!{ o?.parse::<u32>().ok()?.into_ok_or_into::<MyErr>().into_some() };
+    assert_eq!(Some(Ok(7)), parsed);
+}
+
+#[test]
+fn into_some_assume_init() {
+    use fns::into_some_assume_init;
+    use std::mem::MaybeUninit;
+    let mut name = MaybeUninit::<String>::uninit();
+    name.write("foo".to_string());
+    let res = unsafe { into_some_assume_init(name) };
+    assert_eq!(Some("foo".to_string()), res);
+    let init = MaybeUninit::new(42);
+    assert_eq!(Some(42), unsafe { into_some_assume_init(init) });
+}
+
+#[test]
+fn option_err_if_some() {
+    assert_eq!(Ok(()), None::<u32>.err_if_some("present"));
+    assert_eq!(Err("present"), Some(42).err_if_some("present"));
+}
+
+#[test]
+fn bool_to_result() {
+    use fns::bool_to_result;
+    assert_eq!(Ok(()), bool_to_result(true));
+    assert_eq!(Err(()), bool_to_result(false));
+    assert_eq!(Ok::<_, ()>(true), true.into_ok());
+}
+
+#[cfg(feature = "derive")]
+#[derive(Debug, PartialEq, IntoSome)]
+struct DerivedId(u32);
+
+#[cfg(feature = "derive")]
+#[derive(Debug, PartialEq, IntoSome)]
+#[into_some(none_if = "self.0 == 0")]
+struct SentinelId(u32);
+
+#[test]
+#[cfg(feature = "derive")]
+fn derive_into_some() {
+    assert_eq!(Some(DerivedId(0)), DerivedId(0).into_some());
+    assert_eq!(Some(SentinelId(42)), SentinelId(42).into_some());
+    assert_eq!(None, SentinelId(0).into_some());
+    let o = Some("0");
+    let id = fn_expr!{ { SentinelId(o?.parse().ok()?) }.into_some() };
+    assert_eq!(None, id);
+}
+
+#[cfg(feature = "derive")]
+#[derive(Debug, PartialEq, IntoOk)]
+struct DerivedName(String);
+
+#[cfg(feature = "derive")]
+#[derive(Debug, PartialEq, IntoOk)]
+#[into_ok(error = "MissingErr")]
+struct DerivedPort(u16);
+
+#[test]
+#[cfg(feature = "derive")]
+fn derive_into_ok() {
+    let name = DerivedName("foo".to_string()).into_ok::<()>();
+    assert_eq!(Ok(DerivedName("foo".to_string())), name);
+    let o = Some("8080");
+    let port = fn_expr!{ { DerivedPort(o.ok_or(MissingErr)?.parse()?) }.into_ok() };
+    assert_eq!(Ok(DerivedPort(8080)), port);
+    let none: Option<&str> = None;
+    let missing = fn_expr!{ { DerivedPort(none.ok_or(MissingErr)?.parse()?) }.into_ok() };
+    assert_eq!(Err(MissingErr), missing);
+}
+
+#[test]
+fn fn_search_first_some_wins() {
+    let mut evaluated = Vec::new();
+    let names = ["", "foo", "bar"];
+    let found = fn_search!{
+        try { evaluated.push(0); names.first()?.chars().next() };
+        try { evaluated.push(1); names.get(1)?.chars().next() };
+        try { evaluated.push(2); names.get(2)?.chars().next() };
+        None
+    };
+    assert_eq!(Some('f'), found);
+    assert_eq!(vec![0, 1], evaluated);
+}
+
+#[test]
+#[allow(clippy::redundant_closure_call)]
+fn fn_search_fallback() {
+    let empty: Vec<&str> = Vec::new();
+    let found = fn_search!{
+        try empty.first()?.parse::<u32>().ok();
+        try Some(empty.get(1)?.len() as u32);
+        Some(0)
+    };
+    assert_eq!(Some(0), found);
+    let res: Result<u32, ::std::num::ParseIntError> = fn_search!{
+        try "x".parse::<u32>();
+        try "42".parse::<u32>();
+        "0".parse::<u32>()
+    };
+    assert_eq!(Ok(42), res);
+}
+
+#[test]
+fn result_and_then_into() {
+    #[derive(Debug, PartialEq)]
+    enum StepErr {
+        First(u32),
+        Second(String),
+    }
+    impl From<InnerErr> for StepErr {
+        fn from(e: InnerErr) -> StepErr {
+            StepErr::First(e.0)
+        }
+    }
+    impl From<OuterErr> for StepErr {
+        fn from(e: OuterErr) -> StepErr {
+            StepErr::Second(e.0)
+        }
+    }
+    let check = |v: u32| if v > 1 { Ok(v * 2) } else { Err(OuterErr("too small".to_string())) };
+    let ok: Result<u32, StepErr> = Ok::<_, InnerErr>(21).and_then_into(check);
+    assert_eq!(Ok(42), ok);
+    let first: Result<u32, StepErr> = Err(InnerErr(7)).and_then_into(check);
+    assert_eq!(Err(StepErr::First(7)), first);
+    let second: Result<u32, StepErr> = Ok::<_, InnerErr>(1).and_then_into(check);
+    assert_eq!(Err(StepErr::Second("too small".to_string())), second);
 }